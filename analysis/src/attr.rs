@@ -0,0 +1,304 @@
+//! Parsing of `#[ordinal(...)]` helper attributes.
+
+use proc_macro2::Span;
+use syn::spanned::Spanned;
+use syn::{Attribute, Error, Ident, Lit, Meta, NestedMeta, Result, Visibility};
+
+/// Options given in `#[ordinal(...)]` attributes on the enum.
+#[derive(Default)]
+pub struct EnumAttrs {
+    /// Generate `from_ordinal` even if some variants carry data,
+    /// which is only possible if those variants are flattened.
+    pub from_ordinal: bool,
+    /// The name of the fieldless mirror enum to generate.
+    pub kind: Option<Ident>,
+    /// The integer type of ordinals, taken from `#[repr]`
+    /// if not given explicitly.
+    pub repr: Option<Ident>,
+    /// Require every variant to have an explicit index.
+    pub explicit: bool,
+    /// Keep the ordinals of variants disabled by `#[cfg]` reserved.
+    pub stable_cfg: bool,
+    /// What `ordinal()` does for skipped variants.
+    pub on_skip: OnSkip,
+    /// Implement `Serialize` and `Deserialize` by ordinal.
+    pub serde: Option<Span>,
+    /// Implement the comparison traits by ordinal.
+    pub ord: Option<(OrdBy, Span)>,
+    /// The name of the inherent method returning the ordinal.
+    pub method: Option<Ident>,
+    /// The visibility of the generated inherent items.
+    pub vis: Option<Visibility>,
+    /// Extra attributes for the generated inherent methods.
+    pub attrs: Vec<Meta>,
+    /// The lockfile to check the ordinals against, relative
+    /// to the crate's manifest directory.
+    pub lock: Option<syn::LitStr>,
+}
+
+/// How `#[ordinal(ord)]` compares values of the same variant.
+#[derive(Clone, Copy, PartialEq)]
+pub enum OrdBy {
+    /// Values of the same variant are equal.
+    Ordinal,
+    /// Values of the same variant are compared by their fields.
+    Fields,
+}
+
+/// What `ordinal()` does for variants with `#[ordinal(skip)]`.
+#[derive(Default)]
+pub enum OnSkip {
+    /// Return an `Option`, which is `None` for skipped variants.
+    #[default]
+    None,
+    /// Panic.
+    Panic,
+    /// Return the given ordinal.
+    Fallback(usize, Span),
+}
+
+impl EnumAttrs {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut parsed = EnumAttrs::default();
+        let mut seen = Vec::new();
+
+        for meta in ordinal_metas(attrs)? {
+            let key = check_key(&meta, &mut seen)?;
+            match key.as_str() {
+                "from_ordinal" => parsed.from_ordinal = parse_flag(&meta)?,
+                "kind" => parsed.kind = Some(parse_ident(&meta)?),
+                "repr" => parsed.repr = Some(parse_repr(&meta)?),
+                "explicit" => parsed.explicit = parse_flag(&meta)?,
+                "stable_cfg" => parsed.stable_cfg = parse_flag(&meta)?,
+                "on_skip" => parsed.on_skip = parse_on_skip(&meta)?,
+                "serde" => {
+                    parse_flag(&meta)?;
+                    parsed.serde = Some(meta.span());
+                }
+                "ord" => parsed.ord = Some((parse_ord(&meta)?, meta.span())),
+                "method" => parsed.method = Some(parse_ident(&meta)?),
+                "vis" => parsed.vis = Some(parse_vis(&meta)?),
+                "attrs" => parsed.attrs = parse_list(&meta)?,
+                "lock" => parsed.lock = Some(parse_str(&meta)?),
+                _ => return Err(unknown_key(&meta, &key)),
+            }
+        }
+
+        if parsed.repr.is_none() {
+            parsed.repr = primitive_repr(attrs);
+        }
+
+        Ok(parsed)
+    }
+}
+
+/// Options given in `#[ordinal(...)]` attributes on a variant.
+#[derive(Default)]
+pub struct VariantAttrs {
+    /// The explicit ordinal of the variant.
+    pub index: Option<(usize, Span)>,
+    /// Leave the variant out of the ordinal space.
+    pub skip: bool,
+    /// Give each variant of the wrapped enum its own ordinal.
+    pub flatten: Option<Span>,
+}
+
+impl VariantAttrs {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut parsed = VariantAttrs::default();
+        let mut seen = Vec::new();
+
+        for meta in ordinal_metas(attrs)? {
+            let key = check_key(&meta, &mut seen)?;
+            match key.as_str() {
+                "index" => parsed.index = Some(parse_usize(&meta)?),
+                "skip" => parsed.skip = parse_flag(&meta)?,
+                "flatten" => {
+                    parse_flag(&meta)?;
+                    parsed.flatten = Some(meta.span());
+                }
+                _ => return Err(unknown_key(&meta, &key)),
+            }
+        }
+
+        Ok(parsed)
+    }
+}
+
+/// The integer types which ordinals may be represented as.
+const REPRS: &[&str] = &["u8", "u16", "u32", "u64", "usize"];
+
+/// Finds an unsigned integer type in the enum's `#[repr]`
+/// attribute, if it has one.
+fn primitive_repr(attrs: &[Attribute]) -> Option<Ident> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("repr"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => Some(list.nested),
+            _ => None,
+        })
+        .flatten()
+        .find_map(|nested| match nested {
+            NestedMeta::Meta(Meta::Path(path)) => path
+                .get_ident()
+                .filter(|ident| REPRS.iter().any(|repr| ident == repr))
+                .cloned(),
+            _ => None,
+        })
+}
+
+/// Collects the options of every `#[ordinal(...)]` attribute.
+fn ordinal_metas(attrs: &[Attribute]) -> Result<Vec<NestedMeta>> {
+    let mut metas = Vec::new();
+
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("ordinal")) {
+        match attr.parse_meta()? {
+            Meta::List(list) => metas.extend(list.nested),
+            meta => return Err(Error::new_spanned(meta, "expected `#[ordinal(...)]`")),
+        }
+    }
+
+    Ok(metas)
+}
+
+/// Returns the name of an option, failing if it was
+/// already given.
+fn check_key(meta: &NestedMeta, seen: &mut Vec<String>) -> Result<String> {
+    let path = match meta {
+        NestedMeta::Meta(meta) => meta.path(),
+        NestedMeta::Lit(lit) => return Err(Error::new_spanned(lit, "expected an option name")),
+    };
+    let key = match path.get_ident() {
+        Some(ident) => ident.to_string(),
+        None => return Err(Error::new_spanned(path, "expected an option name")),
+    };
+
+    if seen.contains(&key) {
+        return Err(Error::new_spanned(
+            path,
+            format!("duplicate option `{}`", key),
+        ));
+    }
+    seen.push(key.clone());
+
+    Ok(key)
+}
+
+fn unknown_key(meta: &NestedMeta, key: &str) -> Error {
+    Error::new_spanned(meta, format!("unknown `ordinal` option `{}`", key))
+}
+
+/// Parses an option which takes no value, such as `from_ordinal`.
+fn parse_flag(meta: &NestedMeta) -> Result<bool> {
+    match meta {
+        NestedMeta::Meta(Meta::Path(_)) => Ok(true),
+        _ => Err(Error::new_spanned(
+            meta,
+            "this option does not take a value",
+        )),
+    }
+}
+
+/// Parses an option whose value is a string literal,
+/// such as `kind = "AnimalKind"`.
+fn parse_str(meta: &NestedMeta) -> Result<syn::LitStr> {
+    match meta {
+        NestedMeta::Meta(Meta::NameValue(name_value)) => match &name_value.lit {
+            Lit::Str(lit) => Ok(lit.clone()),
+            lit => Err(Error::new_spanned(lit, "expected a string literal")),
+        },
+        _ => Err(Error::new_spanned(meta, "expected a string value")),
+    }
+}
+
+/// Parses an option whose value is a list of attributes,
+/// such as `attrs(inline, must_use)`.
+fn parse_list(meta: &NestedMeta) -> Result<Vec<Meta>> {
+    match meta {
+        NestedMeta::Meta(Meta::List(list)) => list
+            .nested
+            .iter()
+            .map(|nested| match nested {
+                NestedMeta::Meta(meta) => Ok(meta.clone()),
+                NestedMeta::Lit(lit) => Err(Error::new_spanned(lit, "expected an attribute")),
+            })
+            .collect(),
+        _ => Err(Error::new_spanned(meta, "expected a list of attributes")),
+    }
+}
+
+/// Parses an option whose value is an integer literal,
+/// such as `index = 3`.
+fn parse_usize(meta: &NestedMeta) -> Result<(usize, Span)> {
+    match meta {
+        NestedMeta::Meta(Meta::NameValue(name_value)) => match &name_value.lit {
+            Lit::Int(lit) => Ok((lit.base10_parse()?, lit.span())),
+            lit => Err(Error::new_spanned(lit, "expected an integer literal")),
+        },
+        _ => Err(Error::new_spanned(meta, "expected an integer value")),
+    }
+}
+
+/// Parses the `on_skip` option, which is either `"none"`,
+/// `"panic"` or a fallback ordinal.
+fn parse_on_skip(meta: &NestedMeta) -> Result<OnSkip> {
+    if let NestedMeta::Meta(Meta::NameValue(name_value)) = meta {
+        if let Lit::Int(_) = name_value.lit {
+            let (ordinal, span) = parse_usize(meta)?;
+            return Ok(OnSkip::Fallback(ordinal, span));
+        }
+    }
+
+    let lit = parse_str(meta)?;
+    match lit.value().as_str() {
+        "none" => Ok(OnSkip::None),
+        "panic" => Ok(OnSkip::Panic),
+        _ => Err(Error::new_spanned(
+            lit,
+            "expected `none`, `panic` or a fallback ordinal",
+        )),
+    }
+}
+
+/// Parses the `ord` option, which is either a flag or
+/// one of `"ordinal"` and `"fields"`.
+fn parse_ord(meta: &NestedMeta) -> Result<OrdBy> {
+    if let NestedMeta::Meta(Meta::Path(_)) = meta {
+        return Ok(OrdBy::Ordinal);
+    }
+
+    let lit = parse_str(meta)?;
+    match lit.value().as_str() {
+        "ordinal" => Ok(OrdBy::Ordinal),
+        "fields" => Ok(OrdBy::Fields),
+        _ => Err(Error::new_spanned(lit, "expected `ordinal` or `fields`")),
+    }
+}
+
+/// Parses an option whose value is an identifier in a string literal.
+fn parse_ident(meta: &NestedMeta) -> Result<Ident> {
+    parse_str(meta)?.parse()
+}
+
+/// Parses the `vis` option, which must be a visibility
+/// such as `pub(crate)`, or empty for private items.
+fn parse_vis(meta: &NestedMeta) -> Result<Visibility> {
+    let lit = parse_str(meta)?;
+    lit.parse()
+        .map_err(|_| Error::new_spanned(lit, "expected a visibility such as `pub(crate)`"))
+}
+
+/// Parses the `repr` option, which must name one of the
+/// unsigned integer types.
+fn parse_repr(meta: &NestedMeta) -> Result<Ident> {
+    let lit = parse_str(meta)?;
+    let repr: Ident = lit.parse()?;
+    if !REPRS.iter().any(|name| repr == name) {
+        return Err(Error::new_spanned(
+            lit,
+            format!("expected one of {}", REPRS.join(", ")),
+        ));
+    }
+    Ok(repr)
+}
@@ -0,0 +1,23 @@
+//! Variant detection and ordinal assignment for `ordinalizer`.
+//!
+//! This crate holds the analysis behind `#[derive(Ordinal)]`,
+//! so that tools reading Rust sources number variants exactly
+//! like the derive does. Errors are returned as [`syn::Error`]s,
+//! several of which may be combined into one.
+
+pub mod attr;
+pub mod variant;
+
+pub use attr::{EnumAttrs, OnSkip, OrdBy, VariantAttrs};
+pub use variant::{detect_variants, Variant};
+
+use proc_macro2::Ident;
+
+/// Returns the name of a variant as written in the source,
+/// without any `r#` prefix.
+pub fn variant_name(variant_ident: &Ident) -> String {
+    variant_ident
+        .to_string()
+        .trim_start_matches("r#")
+        .to_owned()
+}
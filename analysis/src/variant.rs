@@ -0,0 +1,252 @@
+//! Detection of an enum's variants and their ordinals.
+
+use crate::attr::{EnumAttrs, VariantAttrs};
+use proc_macro2::Ident;
+use syn::{DeriveInput, Error, Result};
+
+pub struct Variant<'a> {
+    pub ident: &'a Ident,
+    pub attrs: &'a [syn::Attribute],
+    pub fields: &'a syn::Fields,
+    pub unit_field_count: usize,
+    pub has_named_fields: bool,
+    /// The `#[cfg]` attributes on the variant.
+    pub cfgs: Vec<&'a syn::Attribute>,
+    /// Options given in `#[ordinal(...)]` attributes on the variant.
+    pub options: VariantAttrs,
+    /// The ordinal of the variant, or `None` if it is skipped.
+    /// In enums with flattened variants, this is the position of
+    /// the variant among the numbered variants instead.
+    pub ordinal: Option<usize>,
+    /// The field of a `#[ordinal(flatten)]` variant.
+    pub flatten: Option<&'a syn::Field>,
+}
+
+/// Collects errors, so that as many as possible
+/// are reported at once.
+#[derive(Default)]
+struct Errors(Option<Error>);
+
+impl Errors {
+    fn push(&mut self, error: Error) {
+        match &mut self.0 {
+            Some(errors) => errors.combine(error),
+            None => self.0 = Some(error),
+        }
+    }
+
+    fn finish(self) -> Result<()> {
+        match self.0 {
+            Some(errors) => Err(errors),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Detects the variants of an enum and assigns their ordinals.
+pub fn detect_variants<'a>(input: &'a DeriveInput, attrs: &EnumAttrs) -> Result<Vec<Variant<'a>>> {
+    let mut vec = Vec::new();
+    let mut errors = Errors::default();
+
+    let data = match &input.data {
+        syn::Data::Enum(data) => data,
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "cannot derive `Ordinal` on an item which is not an enum",
+            ))
+        }
+    };
+
+    for variant in &data.variants {
+        vec.push(detect_variant(variant, &mut errors));
+    }
+
+    assign_ordinals(&mut vec, attrs, &mut errors);
+
+    if vec.iter().any(|v| v.flatten.is_some()) {
+        check_flatten(&vec, input, attrs, &mut errors);
+    }
+
+    errors.finish()?;
+    Ok(vec)
+}
+
+fn detect_variant<'a>(variant: &'a syn::Variant, errors: &mut Errors) -> Variant<'a> {
+    let ident = &variant.ident;
+
+    let (unit_field_count, has_named_fields) = match &variant.fields {
+        syn::Fields::Named(_) => (0, true),
+        syn::Fields::Unit => (0, false),
+        syn::Fields::Unnamed(unnanmed) => (unnanmed.unnamed.len(), false),
+    };
+
+    let options = VariantAttrs::parse(&variant.attrs).unwrap_or_else(|err| {
+        errors.push(err);
+        VariantAttrs::default()
+    });
+
+    let flatten = options.flatten.and_then(|span| {
+        if variant.fields.len() != 1 {
+            errors.push(Error::new(
+                span,
+                "a flattened variant must have exactly one field",
+            ));
+            return None;
+        }
+        if options.skip {
+            errors.push(Error::new(span, "a skipped variant cannot be flattened"));
+            return None;
+        }
+        variant.fields.iter().next()
+    });
+
+    Variant {
+        ident,
+        attrs: &variant.attrs,
+        fields: &variant.fields,
+        unit_field_count,
+        has_named_fields,
+        cfgs: variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("cfg"))
+            .collect(),
+        options,
+        ordinal: None,
+        flatten,
+    }
+}
+
+/// Numbers the variants. A variant without an explicit index
+/// follows the previous variant, like enum discriminants.
+fn assign_ordinals(variants: &mut [Variant], attrs: &EnumAttrs, errors: &mut Errors) {
+    let mut next = 0;
+    for variant in variants.iter_mut() {
+        if variant.options.skip {
+            if let Some((_, span)) = variant.options.index {
+                errors.push(Error::new(span, "a skipped variant cannot have an index"));
+            }
+            continue;
+        }
+
+        let ordinal = match variant.options.index {
+            Some((index, _)) => index,
+            None => {
+                if attrs.explicit {
+                    errors.push(Error::new_spanned(
+                        variant.ident,
+                        format!(
+                            "variant `{}` has no `#[ordinal(index = ...)]`, which every \
+                             variant of an `#[ordinal(explicit)]` enum needs",
+                            variant.ident
+                        ),
+                    ));
+                }
+                next
+            }
+        };
+        variant.ordinal = Some(ordinal);
+        next = ordinal + 1;
+    }
+
+    let numbered: Vec<&Variant> = variants.iter().filter(|v| v.ordinal.is_some()).collect();
+
+    for (i, variant) in numbered.iter().enumerate() {
+        if let Some(other) = numbered[..i].iter().find(|v| v.ordinal == variant.ordinal) {
+            errors.push(Error::new(
+                index_span(variant),
+                format!(
+                    "ordinal {} is already used by variant `{}`",
+                    variant.ordinal.unwrap(),
+                    other.ident
+                ),
+            ));
+        }
+    }
+
+    if attrs.stable_cfg {
+        check_stable_cfg(&numbered, errors);
+        return;
+    }
+
+    let max = numbered.iter().filter_map(|v| v.ordinal).max().unwrap_or(0);
+    let gap = (0..max).find(|&ordinal| numbered.iter().all(|v| v.ordinal != Some(ordinal)));
+    if let Some(gap) = gap {
+        let after = numbered
+            .iter()
+            .filter(|v| v.ordinal > Some(gap))
+            .min_by_key(|v| v.ordinal)
+            .expect("a gap implies a larger ordinal");
+        errors.push(Error::new(
+            index_span(after),
+            format!(
+                "ordinals must be contiguous, but no variant has ordinal {}",
+                gap
+            ),
+        ));
+    }
+}
+
+/// Checks that no ordinal depends on whether a variant behind
+/// `#[cfg]` is enabled. Disabled variants are removed before the
+/// derive runs, so their ordinals show up as gaps, which are allowed.
+fn check_stable_cfg(numbered: &[&Variant], errors: &mut Errors) {
+    for pair in numbered.windows(2) {
+        let (previous, variant) = (pair[0], pair[1]);
+        if !previous.cfgs.is_empty() && variant.options.index.is_none() {
+            errors.push(Error::new_spanned(
+                variant.ident,
+                format!(
+                    "variant `{}` needs an explicit `#[ordinal(index = ...)]`, since it \
+                     follows `{}`, which is behind `#[cfg]`, so its ordinal would change \
+                     when `{}` is disabled",
+                    variant.ident, previous.ident, previous.ident
+                ),
+            ));
+        }
+    }
+}
+
+/// Rejects options which need the ordinals of all
+/// variants to be known when the derive runs.
+fn check_flatten(
+    variants: &[Variant],
+    input: &DeriveInput,
+    attrs: &EnumAttrs,
+    errors: &mut Errors,
+) {
+    if !input.generics.params.is_empty() {
+        errors.push(Error::new_spanned(
+            &input.generics,
+            "`#[ordinal(flatten)]` is not supported on generic enums",
+        ));
+    }
+    if attrs.explicit || attrs.stable_cfg {
+        errors.push(Error::new_spanned(
+            &input.ident,
+            "`#[ordinal(flatten)]` cannot be combined with `explicit` or `stable_cfg`",
+        ));
+    }
+    if let Some(kind) = &attrs.kind {
+        errors.push(Error::new_spanned(
+            kind,
+            "`#[ordinal(flatten)]` cannot be combined with `kind`",
+        ));
+    }
+    for (_, span) in variants.iter().filter_map(|v| v.options.index) {
+        errors.push(Error::new(
+            span,
+            "explicit indices cannot be combined with `#[ordinal(flatten)]`",
+        ));
+    }
+}
+
+/// Returns the span of a variant's explicit index,
+/// or of its name if it has none.
+fn index_span(variant: &Variant) -> proc_macro2::Span {
+    match variant.options.index {
+        Some((_, span)) => span,
+        None => variant.ident.span(),
+    }
+}
@@ -0,0 +1,1432 @@
+//! Derive macro for the `ordinalizer` crate.
+//!
+//! This crate is an implementation detail; use the
+//! `Ordinal` derive re-exported from `ordinalizer` instead.
+
+mod lock;
+
+use ordinalizer_analysis::{detect_variants, variant_name, EnumAttrs, OnSkip, OrdBy, Variant};
+use proc_macro2::{Ident, Literal, Span, TokenStream};
+use proc_macro_error::*;
+use quote::{quote, quote_spanned};
+use syn::{parse_macro_input, parse_quote, parse_quote_spanned, spanned::Spanned, DeriveInput};
+
+/// Implements `ordinalizer::Ordinal` for an enum.
+///
+/// The enum may have any number of variants. It is not
+/// required to be a C-like enum, i.e. its variants
+/// may have named or unnamed fields.
+///
+/// The returned ordinals will correspond to the variant's
+/// index in the enum definition. For example, the first
+/// variant of enum will have ordinal `0`.
+///
+/// A variant's ordinal can be set explicitly with
+/// `#[ordinal(index = N)]`; the variants following it are then
+/// numbered from `N + 1`, like enum discriminants. The ordinals of
+/// all variants must be distinct and contiguous from `0`. With
+/// `#[ordinal(explicit)]` on the enum, every variant must have an
+/// explicit index, so reordering the variants never changes
+/// their ordinals.
+///
+/// Generic enums are supported; the generated impl carries
+/// over the enum's generic parameters and where-clause.
+///
+/// The enum also gets inherent `VARIANT_COUNT` and `MAX_ORDINAL`
+/// constants and an inherent `const fn ordinal`, which can be used
+/// in const contexts such as array lengths without importing the trait.
+///
+/// Each variant's ordinal is also available without constructing
+/// a value through an associated constant named after the variant,
+/// e.g. `ORDINAL_CAT` for a variant `Cat` or `ORDINAL_NET_MSG` for
+/// a variant `NetMsg`.
+///
+/// If no numbered variant carries data, a `const fn from_ordinal(usize) -> Option<Self>`
+/// is generated as well, along with impls of `ordinalizer::FromOrdinal`
+/// and `TryFrom<usize>`. Enums with data-carrying variants can request
+/// these with `#[ordinal(from_ordinal)]`, which fails with an error on
+/// each variant carrying data, since such a variant cannot be built from
+/// its ordinal alone. Flattened variants are the exception, as they are
+/// built from the ordinals of the enums they wrap.
+///
+/// Variants disabled by `#[cfg]` are removed before the derive runs,
+/// so they take up no ordinal and the variants after them move down.
+/// With `#[ordinal(stable_cfg)]`, ordinals do not depend on which cfgs
+/// are active: every variant following one behind `#[cfg]` must have
+/// an explicit index, and the ordinals of disabled variants are left
+/// unused instead of being rejected as gaps. `VARIANT_COUNT` is then
+/// one more than the largest ordinal in use.
+///
+/// A variant marked `#[ordinal(skip)]` has no ordinal; it is left out
+/// of the numbering, `VARIANT_COUNT`, `VARIANT_NAMES` and `from_ordinal`.
+/// What `ordinal()` does for such a variant is chosen on the enum with
+/// `#[ordinal(on_skip = ...)]`:
+/// * `"none"` (the default): `ordinal()` returns an `Option`, which is
+///   `None` for skipped variants. The `Ordinal` trait is not implemented
+///   in this case, since it requires every value to have an ordinal.
+/// * `"panic"`: `ordinal()` panics for skipped variants.
+/// * an integer: `ordinal()` returns that ordinal for skipped variants.
+///
+/// `#[ordinal(repr = "u8")]` makes the inherent `ordinal()`,
+/// `MAX_ORDINAL`, the `ORDINAL_*` constants and `from_ordinal` use
+/// `u8` instead of `usize`; `u16`, `u32`, `u64` and `usize` are
+/// accepted as well. Without this option, the type is taken from
+/// the enum's `#[repr]` if it is one of those types. The derive fails
+/// if the variants do not fit into the chosen type.
+///
+/// Fieldless enums also get `const ALL: [Self; N]`, holding every
+/// variant in ordinal order, and `fn all()`, which iterates over them.
+/// Skipped variants are left out of both. Such enums can also be
+/// stepped through in ordinal order with `first()`, `last()`, `next()`
+/// and `prev()`, and cyclically with `wrapping_next()` and
+/// `wrapping_prev()`; `next()` and `prev()` return `None` for skipped
+/// variants, so the wrapping methods return `first()` and `last()`.
+///
+/// The names of the variants are available through `VARIANT_NAMES`,
+/// indexed by ordinal, and through `fn variant_name(&self) -> &'static str`.
+/// Ordinals which no variant uses, as with `stable_cfg`, have empty names.
+/// `const fn same_variant(&self, other: &Self) -> bool` compares
+/// the variants of two values, ignoring their fields.
+///
+/// `VARIANTS` holds an `ordinalizer::VariantInfo` for each variant,
+/// skipped ones included, in declaration order. It records the name,
+/// ordinal and fields of the variant, and the one of a value's variant
+/// is returned by `const fn variant_info(&self) -> &'static VariantInfo`.
+///
+/// The derive also implements `ordinalizer::set::OrdinalBits`, so
+/// sets of variants can be stored in an `OrdinalSet`, and generates
+/// a `const fn ordinal_set(&[Self]) -> OrdinalSet<Self>` for building
+/// such sets in const contexts. Likewise, it implements
+/// `ordinalizer::map::OrdinalSlots`, which gives an `OrdinalMap`
+/// keyed by the enum an inline array of `VARIANT_COUNT` slots.
+///
+/// With the `serde` feature of `ordinalizer`, `#[ordinal(serde)]`
+/// implements `Serialize` and `Deserialize` for enums without fields,
+/// representing each variant by its ordinal as the `repr` type.
+/// Deserializing an ordinal which belongs to no variant fails. Since
+/// skipped variants could not be told apart from the variant with the
+/// fallback ordinal, serde requires `on_skip` to be `panic` if any
+/// variant is skipped.
+///
+/// `#[ordinal(ord)]` implements `PartialEq`, `Eq`, `PartialOrd` and
+/// `Ord` by ordinal, without requiring anything of the fields: values
+/// of the same variant are equal. With `#[ordinal(ord = "fields")]`,
+/// values of the same variant are instead compared by their fields,
+/// in declaration order, which requires the fields to be `Ord`. Both
+/// require `on_skip` to be set if any variant is skipped, and fields
+/// may only be compared with `on_skip = "panic"`, since a skipped
+/// variant would otherwise equal the variant with the fallback ordinal.
+///
+/// A variant with a single field of another `Ordinal` enum can be
+/// marked `#[ordinal(flatten)]` to give each variant of the wrapped
+/// enum its own ordinal, so that e.g. `Msg::Net(NetMsg::Pong)` is
+/// numbered after `Msg::Net(NetMsg::Ping)`. `VARIANT_COUNT` is then
+/// the sum of the counts of the variants, `VARIANT_NAMES` contains
+/// the names of the wrapped variants and `ordinal()`, `variant_name()`
+/// and `same_variant()` are no longer `const`. Values of a flattened
+/// variant are only of the same variant if their fields are.
+/// `from_ordinal` requires the wrapped enum to implement
+/// `ordinalizer::FromOrdinal`. Since the ordinals are only known at
+/// compile time, flattening cannot be combined with explicit indices,
+/// `stable_cfg`, `kind`, `lock` or generic parameters.
+///
+/// `#[ordinal(method = "index")]` names the inherent method returning
+/// the ordinal `index` instead of `ordinal`, and `#[ordinal(vis = "pub(crate)")]`
+/// sets the visibility of the generated inherent items, which are `pub`
+/// by default. Attributes given in `#[ordinal(attrs(...))]`, such as
+/// `inline`, `must_use` or `doc = "..."`, are added to every generated
+/// inherent method, including `variant_name()` and `from_ordinal()`.
+///
+/// `#[ordinal(lock = "ordinals.lock")]` checks the ordinals against
+/// a lockfile, relative to the crate's `Cargo.toml`, which lists the
+/// ordinal of each variant by name. The derive fails with a diff of
+/// the ordinals if a variant was moved to another ordinal, removed
+/// or skipped, or if its ordinal was taken by another variant.
+/// Building with the environment variable `ORDINALIZER_UPDATE_LOCK`
+/// set writes the current ordinals to the lockfile instead, creating
+/// it if needed. Enums are listed in the lockfile by name, so enums
+/// sharing a lockfile need distinct names. With `stable_cfg`, a
+/// locked variant which is missing is assumed to be cfg'd out, as
+/// long as no other variant took its ordinal.
+///
+/// `#[ordinal(kind = "AnimalKind")]` generates a fieldless enum
+/// named `AnimalKind` with the same variants, deriving `Ordinal`,
+/// `Copy`, `Eq`, `Hash` and `Ord`, along with a
+/// `const fn kind(&self) -> AnimalKind` method on the original enum.
+#[proc_macro_error]
+#[proc_macro_derive(Ordinal, attributes(ordinal))]
+pub fn derive_ordinal(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let attrs = EnumAttrs::parse(&input.attrs).unwrap_or_else(|err| abort!(err));
+
+    let variants = detect_variants(&input, &attrs).unwrap_or_else(|err| abort!(err));
+
+    let repr = attrs
+        .repr
+        .clone()
+        .unwrap_or_else(|| Ident::new("usize", Span::call_site()));
+    let method = attrs
+        .method
+        .clone()
+        .unwrap_or_else(|| Ident::new("ordinal", Span::call_site()));
+    let vis = attrs.vis.clone().unwrap_or_else(|| parse_quote! { pub });
+    let method_attrs = &attrs.attrs;
+    let fn_attrs = quote! { #(#[#method_attrs])* };
+    let variant_count = variants
+        .iter()
+        .filter_map(|v| v.ordinal)
+        .map(|ordinal| ordinal + 1)
+        .max()
+        .unwrap_or(0);
+
+    // The number of ordinals of a flattened variant is only known
+    // once its type is, so such enums check their ordinals with
+    // const assertions instead.
+    let flattened = variants.iter().any(|v| v.flatten.is_some());
+    let count = if flattened {
+        let widths = variants
+            .iter()
+            .filter(|v| v.ordinal.is_some())
+            .map(ordinal_width);
+        quote! { 0 #(+ #widths)* }
+    } else {
+        let count = Literal::usize_unsuffixed(variant_count);
+        quote! { #count }
+    };
+    if !flattened {
+        check_repr_fits(&repr, variant_count);
+    }
+
+    let has_skipped = variants.iter().any(|v| v.ordinal.is_none());
+    if let OnSkip::Fallback(fallback, span) = attrs.on_skip {
+        if fallback >= variant_count && !flattened {
+            emit_error!(
+                span,
+                "fallback ordinal {} is not the ordinal of any variant",
+                fallback
+            );
+        }
+    }
+
+    // Without a fallback, `ordinal()` cannot uphold the trait's
+    // contract of returning an ordinal for every value.
+    let returns_option = has_skipped && matches!(attrs.on_skip, OnSkip::None);
+    let ordinal_ty = if returns_option {
+        quote! { ::core::option::Option<#repr> }
+    } else {
+        quote! { #repr }
+    };
+
+    let match_arms = generate_match_arms(&variants, &input, &repr, &attrs.on_skip, returns_option);
+
+    let ordinal_consts = generate_ordinal_consts(&variants, &repr, &vis);
+
+    let variant_names = generate_variant_names(&variants, variant_count);
+    let variant_names_ty = if flattened {
+        quote! { [&'static str; Self::VARIANT_COUNT] }
+    } else {
+        quote! { [&'static str; #variant_count] }
+    };
+    let name_arms = generate_name_arms(&variants, &input);
+    let same_variant_arms = generate_same_variant_arms(&variants, &input);
+
+    let kind = match &attrs.kind {
+        Some(kind_ident) => generate_kind(kind_ident, &variants, &input, &repr, &attrs, &fn_attrs),
+        None => TokenStream::new(),
+    };
+
+    // Skipped variants are never built from an ordinal,
+    // so only the fields of numbered variants matter.
+    let fieldless = variants
+        .iter()
+        .filter(|v| v.ordinal.is_some())
+        .all(|v| v.fields.is_empty());
+
+    let from_ordinal = if attrs.from_ordinal || fieldless {
+        generate_from_ordinal(&variants, &input, &repr, &vis, &fn_attrs, !returns_option)
+    } else {
+        TokenStream::new()
+    };
+
+    let all = if fieldless {
+        generate_all(&variants, &input, &vis, &fn_attrs)
+    } else {
+        TokenStream::new()
+    };
+    let navigation = if fieldless && variants.iter().any(|v| v.ordinal.is_some()) {
+        generate_navigation(&variants, &input, &vis, &fn_attrs)
+    } else {
+        TokenStream::new()
+    };
+
+    let variant_infos = generate_variant_infos(&variants, &input, &vis, &fn_attrs);
+
+    let serde = match attrs.serde {
+        Some(span) => generate_serde(
+            span,
+            &method,
+            &variants,
+            &input,
+            &repr,
+            &attrs.on_skip,
+            variant_count,
+        ),
+        None => TokenStream::new(),
+    };
+
+    let ord = match attrs.ord {
+        Some((ord_by, span)) => {
+            generate_ord(ord_by, span, &method, &variants, &input, &attrs.on_skip)
+        }
+        None => TokenStream::new(),
+    };
+
+    let enum_ident = &input.ident;
+    let lock = match &attrs.lock {
+        Some(lock) => lock::check_lock(lock, enum_ident, &variants, attrs.stable_cfg),
+        None => TokenStream::new(),
+    };
+    let checks = if flattened {
+        generate_flatten_checks(enum_ident, &repr, &attrs.on_skip)
+    } else {
+        TokenStream::new()
+    };
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // Flattened variants get their ordinals and names
+    // through the `Ordinal` trait, which is not const.
+    let constness = if flattened {
+        TokenStream::new()
+    } else {
+        quote! { const }
+    };
+
+    let ordinal_impl = if returns_option {
+        TokenStream::new()
+    } else {
+        let slot_count = if flattened {
+            quote! { #enum_ident::VARIANT_COUNT }
+        } else {
+            let count = Literal::usize_unsuffixed(variant_count);
+            quote! { #count }
+        };
+        let (bits, set_bit) = if flattened {
+            (
+                quote! { [u64; ::core::primitive::usize::div_ceil(#enum_ident::VARIANT_COUNT, 64)] },
+                quote! { bits[ordinal / 64] |= 1 << (ordinal % 64); },
+            )
+        } else {
+            set_storage(variant_count)
+        };
+        quote! {
+            impl #impl_generics #enum_ident #ty_generics #where_clause {
+                /// Returns the set of the variants of `values`.
+                #fn_attrs
+                #vis #constness fn ordinal_set(values: &[Self]) -> ::ordinalizer::OrdinalSet<Self> {
+                    let mut bits: #bits = ::ordinalizer::set::Bits::EMPTY;
+                    let mut i = 0;
+                    while i < values.len() {
+                        let ordinal = values[i].#method() as usize;
+                        #set_bit
+                        i += 1;
+                    }
+                    ::ordinalizer::OrdinalSet::from_bits(bits)
+                }
+            }
+
+            impl #impl_generics ::ordinalizer::set::OrdinalBits for #enum_ident #ty_generics #where_clause {
+                type Bits = #bits;
+            }
+
+            impl #impl_generics ::ordinalizer::map::OrdinalSlots for #enum_ident #ty_generics #where_clause {
+                type Slots<__Value> = [::core::option::Option<__Value>; #slot_count];
+            }
+
+
+            impl #impl_generics ::ordinalizer::Ordinal for #enum_ident #ty_generics #where_clause {
+                const VARIANT_COUNT: usize = #count;
+
+                const VARIANT_NAMES: &'static [&'static str] = &Self::VARIANT_NAMES;
+
+                fn ordinal(&self) -> usize {
+                    Self::#method(self) as usize
+                }
+
+                fn variant_name(&self) -> &'static str {
+                    Self::variant_name(self)
+                }
+            }
+        }
+    };
+
+    let tokens = quote! {
+        impl #impl_generics #enum_ident #ty_generics #where_clause {
+            /// The number of ordinals used by this enum's variants.
+            #vis const VARIANT_COUNT: usize = #count;
+
+            /// The largest ordinal of any variant in this enum.
+            #vis const MAX_ORDINAL: #repr = (Self::VARIANT_COUNT - 1) as #repr;
+
+            #(#ordinal_consts)*
+
+            /// The names of this enum's variants, indexed by ordinal.
+            #vis const VARIANT_NAMES: #variant_names_ty = #variant_names;
+
+            /// Returns the ordinal of this value's variant.
+            #fn_attrs
+            #vis #constness fn #method(&self) -> #ordinal_ty {
+                match *self {
+                    #(#match_arms,)*
+                }
+            }
+
+            /// Returns the name of this value's variant.
+            #fn_attrs
+            #vis #constness fn variant_name(&self) -> &'static str {
+                match *self {
+                    #(#name_arms,)*
+                }
+            }
+
+            /// Returns whether `self` and `other` are of the same
+            /// variant, regardless of their fields.
+            #fn_attrs
+            #vis #constness fn same_variant(&self, other: &Self) -> bool {
+                #[allow(unreachable_patterns)]
+                match (self, other) {
+                    #(#same_variant_arms,)*
+                    _ => false,
+                }
+            }
+        }
+
+        #ordinal_impl
+
+        #checks
+
+        #lock
+
+        #from_ordinal
+
+        #all
+
+        #navigation
+
+        #variant_infos
+
+        #serde
+
+        #ord
+
+        #kind
+    };
+    tokens.into()
+}
+
+fn generate_match_arms(
+    variants: &[Variant],
+    input: &DeriveInput,
+    repr: &Ident,
+    on_skip: &OnSkip,
+    returns_option: bool,
+) -> Vec<TokenStream> {
+    let mut vec = Vec::new();
+    let enum_ident = &input.ident;
+
+    for variant in variants {
+        let variant_ident = variant.ident;
+        let mut pattern = variant_pattern(enum_ident, variant);
+        let ordinal = match (variant.ordinal, on_skip) {
+            (Some(_), _) => {
+                let const_ident = ordinal_const_ident(variant_ident);
+                let ordinal = match variant.flatten {
+                    Some(field) => {
+                        pattern = flatten_pattern(enum_ident, variant, field);
+                        quote! {
+                            Self::#const_ident + ::ordinalizer::Ordinal::ordinal(inner) as #repr
+                        }
+                    }
+                    None => quote! { Self::#const_ident },
+                };
+                if returns_option {
+                    quote! { ::core::option::Option::Some(#ordinal) }
+                } else {
+                    ordinal
+                }
+            }
+            (None, OnSkip::None) => quote! { ::core::option::Option::None },
+            (None, OnSkip::Panic) => {
+                let message = format!("`{}::{}` has no ordinal", enum_ident, variant_ident);
+                quote! { panic!(#message) }
+            }
+            (None, OnSkip::Fallback(fallback, _)) => {
+                let fallback = Literal::usize_unsuffixed(*fallback);
+                quote! { #fallback }
+            }
+        };
+        let cfgs = &variant.cfgs;
+        vec.push(quote! {
+            #(#cfgs)*
+            #pattern => #ordinal
+        });
+    }
+
+    vec
+}
+
+fn generate_name_arms(variants: &[Variant], input: &DeriveInput) -> Vec<TokenStream> {
+    let enum_ident = &input.ident;
+
+    variants
+        .iter()
+        .map(|variant| {
+            let cfgs = &variant.cfgs;
+            if let (Some(field), Some(_)) = (variant.flatten, variant.ordinal) {
+                let pattern = flatten_pattern(enum_ident, variant, field);
+                return quote! {
+                    #(#cfgs)*
+                    #pattern => ::ordinalizer::Ordinal::variant_name(inner)
+                };
+            }
+
+            let pattern = variant_pattern(enum_ident, variant);
+            let name = variant_name(variant.ident);
+            quote! {
+                #(#cfgs)*
+                #pattern => #name
+            }
+        })
+        .collect()
+}
+
+/// Returns the storage type of an `OrdinalSet` with a bit for each
+/// of `variant_count` ordinals, along with a statement setting the
+/// bit for `ordinal` in a variable `bits` of that type.
+fn set_storage(variant_count: usize) -> (TokenStream, TokenStream) {
+    let primitive = [8, 16, 32, 64, 128]
+        .iter()
+        .find(|&&width| variant_count <= width);
+    match primitive {
+        Some(width) => {
+            let ty = Ident::new(&format!("u{}", width), Span::call_site());
+            (quote! { #ty }, quote! { bits |= 1 << ordinal; })
+        }
+        None => {
+            let words = variant_count.div_ceil(64);
+            (
+                quote! { [u64; #words] },
+                quote! { bits[ordinal / 64] |= 1 << (ordinal % 64); },
+            )
+        }
+    }
+}
+
+fn generate_same_variant_arms(variants: &[Variant], input: &DeriveInput) -> Vec<TokenStream> {
+    let enum_ident = &input.ident;
+
+    variants
+        .iter()
+        .map(|variant| {
+            let cfgs = &variant.cfgs;
+
+            // Values of a flattened variant are of the same variant
+            // only if their fields are of the same inner variant.
+            if let (Some(field), Some(_)) = (variant.flatten, variant.ordinal) {
+                let variant_ident = variant.ident;
+                let member = field_member(field, 0);
+                return quote! {
+                    #(#cfgs)*
+                    (
+                        #enum_ident::#variant_ident { #member: ref a },
+                        #enum_ident::#variant_ident { #member: ref b },
+                    ) => ::ordinalizer::Ordinal::ordinal(a) == ::ordinalizer::Ordinal::ordinal(b)
+                };
+            }
+
+            let pattern = variant_pattern(enum_ident, variant);
+            quote! {
+                #(#cfgs)*
+                (#pattern, #pattern) => true
+            }
+        })
+        .collect()
+}
+
+/// Emits an error if the largest ordinal does not fit in `repr`.
+fn check_repr_fits(repr: &Ident, variant_count: usize) {
+    let max = match repr.to_string().as_str() {
+        "u8" => u8::MAX as u64,
+        "u16" => u16::MAX as u64,
+        "u32" => u32::MAX as u64,
+        _ => return,
+    };
+
+    if variant_count as u64 > max + 1 {
+        emit_error!(
+            repr,
+            "{} variants do not fit in `{}`, which can hold at most {} ordinals",
+            variant_count,
+            repr,
+            max + 1
+        );
+    }
+}
+
+/// Returns a pattern matching any value of a variant.
+fn variant_pattern(enum_ident: &Ident, variant: &Variant) -> TokenStream {
+    let variant_ident = variant.ident;
+    match (variant.has_named_fields, variant.unit_field_count) {
+        (true, _) => quote! { #enum_ident::#variant_ident { .. } },
+        (false, x) if x != 0 => {
+            let underscores: Vec<_> = (0..x).map(|_| quote! { _ }).collect();
+
+            quote! {
+                #enum_ident::#variant_ident(#(#underscores),*)
+            }
+        }
+        (false, 0) => quote! { #enum_ident::#variant_ident },
+        _ => unreachable!(),
+    }
+}
+
+fn generate_ordinal_consts(
+    variants: &[Variant],
+    repr: &Ident,
+    vis: &syn::Visibility,
+) -> Vec<TokenStream> {
+    let mut vec = Vec::new();
+    let mut seen: Vec<(Ident, &Ident)> = Vec::new();
+
+    for variant in variants {
+        if variant.ordinal.is_none() {
+            continue;
+        }
+        let variant_ident = variant.ident;
+        let const_ident = ordinal_const_ident(variant_ident);
+
+        if let Some((_, other)) = seen.iter().find(|(ident, _)| *ident == const_ident) {
+            emit_error!(
+                variant_ident,
+                "variants `{}` and `{}` both map to the constant `{}`",
+                other,
+                variant_ident,
+                const_ident
+            );
+        }
+
+        let doc = if variant.flatten.is_some() {
+            format!("The first ordinal of [`Self::{}`].", variant_ident)
+        } else {
+            format!("The ordinal of [`Self::{}`].", variant_ident)
+        };
+        let ordinal = ordinal_expr(variants, variant, repr);
+        let cfgs = &variant.cfgs;
+        vec.push(quote! {
+            #(#cfgs)*
+            #[doc = #doc]
+            #vis const #const_ident: #repr = #ordinal;
+        });
+        seen.push((const_ident, variant_ident));
+    }
+
+    vec
+}
+
+/// Returns the names of the variants, indexed by ordinal. Ordinals
+/// which no variant uses, as with `stable_cfg`, have empty names.
+fn generate_variant_names(variants: &[Variant], variant_count: usize) -> TokenStream {
+    if variants.iter().all(|v| v.flatten.is_none()) {
+        let mut names = vec![String::new(); variant_count];
+        for variant in variants {
+            if let Some(ordinal) = variant.ordinal {
+                names[ordinal] = variant_name(variant.ident);
+            }
+        }
+        return quote! { [#(#names),*] };
+    }
+
+    // The names of a flattened variant are those of the
+    // variants of the enum it wraps.
+    let assignments = in_ordinal_order(variants).map(|variant| {
+        let const_ident = ordinal_const_ident(variant.ident);
+        match variant.flatten {
+            Some(field) => {
+                let ty = &field.ty;
+                quote! {
+                    let inner = <#ty as ::ordinalizer::Ordinal>::VARIANT_NAMES;
+                    let mut i = 0;
+                    while i < inner.len() {
+                        names[Self::#const_ident as usize + i] = inner[i];
+                        i += 1;
+                    }
+                }
+            }
+            None => {
+                let name = variant_name(variant.ident);
+                quote! { names[Self::#const_ident as usize] = #name; }
+            }
+        }
+    });
+    quote! {
+        {
+            let mut names = [""; Self::VARIANT_COUNT];
+            #(#assignments)*
+            names
+        }
+    }
+}
+
+/// Iterates over variants sorted by their ordinals,
+/// leaving out skipped variants.
+fn in_ordinal_order<'a, 'b>(variants: &'b [Variant<'a>]) -> impl Iterator<Item = &'b Variant<'a>> {
+    let mut sorted: Vec<_> = variants.iter().filter(|v| v.ordinal.is_some()).collect();
+    sorted.sort_by_key(|variant| variant.ordinal);
+    sorted.into_iter()
+}
+
+/// Returns the number of ordinals taken by a variant.
+fn ordinal_width(variant: &Variant) -> TokenStream {
+    match variant.flatten {
+        Some(field) => {
+            let ty = &field.ty;
+            quote! { <#ty as ::ordinalizer::Ordinal>::VARIANT_COUNT }
+        }
+        None => quote! { 1 },
+    }
+}
+
+/// Returns the ordinal of a numbered variant as an expression
+/// of type `repr`. For a flattened variant, this is the ordinal
+/// of the first variant of the enum it wraps.
+fn ordinal_expr(variants: &[Variant], variant: &Variant, repr: &Ident) -> TokenStream {
+    if variants.iter().all(|v| v.flatten.is_none()) {
+        let ordinal = Literal::usize_unsuffixed(variant.ordinal.unwrap());
+        return quote! { #ordinal };
+    }
+
+    let widths = variants
+        .iter()
+        .filter(|v| v.ordinal.is_some() && v.ordinal < variant.ordinal)
+        .map(ordinal_width);
+    quote! { (0 #(+ #widths)*) as #repr }
+}
+
+/// Returns a pattern matching any value of a flattened
+/// variant, binding its field to `inner`.
+fn flatten_pattern(enum_ident: &Ident, variant: &Variant, field: &syn::Field) -> TokenStream {
+    let variant_ident = variant.ident;
+    let member = field_member(field, 0);
+    quote! { #enum_ident::#variant_ident { #member: ref inner } }
+}
+
+/// Returns the name of a field, or its index
+/// for a field of a tuple variant.
+fn field_member(field: &syn::Field, index: usize) -> syn::Member {
+    match &field.ident {
+        Some(ident) => syn::Member::Named(ident.clone()),
+        None => syn::Member::Unnamed(index.into()),
+    }
+}
+
+/// Generates const assertions for the checks which cannot be done
+/// in the derive for enums with flattened variants.
+fn generate_flatten_checks(enum_ident: &Ident, repr: &Ident, on_skip: &OnSkip) -> TokenStream {
+    let mut checks = Vec::new();
+
+    if repr != "usize" && repr != "u64" {
+        let message = format!("the ordinals of `{}` do not fit in `{}`", enum_ident, repr);
+        checks.push(quote! {
+            const _: () = ::core::assert!(
+                #enum_ident::VARIANT_COUNT <= #repr::MAX as usize + 1,
+                #message
+            );
+        });
+    }
+
+    if let OnSkip::Fallback(fallback, span) = on_skip {
+        let fallback = Literal::usize_unsuffixed(*fallback);
+        checks.push(quote_spanned! {*span=>
+            const _: () = ::core::assert!(
+                #fallback < #enum_ident::VARIANT_COUNT,
+                "the fallback ordinal is not the ordinal of any variant"
+            );
+        });
+    }
+
+    quote! { #(#checks)* }
+}
+
+/// Returns the name of the constant holding a variant's ordinal,
+/// e.g. `ORDINAL_NET_MSG` for `NetMsg`.
+fn ordinal_const_ident(variant_ident: &Ident) -> Ident {
+    let name = variant_name(variant_ident);
+    let chars: Vec<char> = name.chars().collect();
+
+    let mut screaming = String::from("ORDINAL_");
+    for (i, &c) in chars.iter().enumerate() {
+        if i > 0 && c.is_uppercase() {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if prev.is_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_uppercase() && next_is_lower)
+            {
+                screaming.push('_');
+            }
+        }
+        screaming.extend(c.to_uppercase());
+    }
+
+    Ident::new(&screaming, variant_ident.span())
+}
+
+fn generate_from_ordinal(
+    variants: &[Variant],
+    input: &DeriveInput,
+    repr: &Ident,
+    vis: &syn::Visibility,
+    fn_attrs: &TokenStream,
+    ordinal_trait: bool,
+) -> TokenStream {
+    let enum_ident = &input.ident;
+
+    // Other than flattened variants, numbered variants with
+    // fields have no values to build from an ordinal.
+    let numbered: Vec<_> = variants
+        .iter()
+        .filter(|v| v.ordinal.is_some())
+        .filter(|variant| {
+            let buildable = variant.fields.is_empty() || variant.flatten.is_some();
+            if !buildable {
+                emit_error!(
+                    variant.ident,
+                    "variant `{}` has fields, so it cannot be built from an ordinal",
+                    variant.ident;
+                    help = "skip the variant with `#[ordinal(skip)]`, or remove `from_ordinal`"
+                );
+            }
+            buildable
+        })
+        .collect();
+
+    // The bounds are spanned to the fields so that a missing
+    // `FromOrdinal` impl points at the flattened variant.
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for variant in &numbered {
+        if let Some(field) = variant.flatten {
+            let ty = &field.ty;
+            where_clause
+                .predicates
+                .push(parse_quote_spanned! { ty.span()=> #ty: ::ordinalizer::FromOrdinal });
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // `FromOrdinal::from_ordinal` cannot be called in a const fn.
+    let constness = if numbered.iter().all(|v| v.flatten.is_none()) {
+        quote! { const }
+    } else {
+        TokenStream::new()
+    };
+
+    let arms = numbered.iter().map(|variant| {
+        let variant_ident = variant.ident;
+        let const_ident = ordinal_const_ident(variant_ident);
+        let cfgs = &variant.cfgs;
+
+        // A flattened variant covers the ordinals of the enum it wraps.
+        if let Some(field) = variant.flatten {
+            let ty = &field.ty;
+            let member = field_member(field, 0);
+            return quote! {
+                #(#cfgs)*
+                ordinal if (ordinal as usize).wrapping_sub(Self::#const_ident as usize)
+                    < <#ty as ::ordinalizer::Ordinal>::VARIANT_COUNT =>
+                {
+                    <#ty as ::ordinalizer::FromOrdinal>::from_ordinal(
+                        ordinal as usize - Self::#const_ident as usize,
+                    )
+                    .map(|inner| #enum_ident::#variant_ident { #member: inner })
+                }
+            };
+        }
+
+        // Named and tuple variants without fields are built with
+        // an empty brace, which works for either.
+        let value = match variant.fields {
+            syn::Fields::Unit => quote! { #enum_ident::#variant_ident },
+            _ => quote! { #enum_ident::#variant_ident {} },
+        };
+
+        quote! {
+            #(#cfgs)*
+            Self::#const_ident => ::core::option::Option::Some(#value)
+        }
+    });
+
+    let from_ordinal_impl = if ordinal_trait {
+        quote! {
+            impl #impl_generics ::ordinalizer::FromOrdinal for #enum_ident #ty_generics #where_clause {
+                fn from_ordinal(ordinal: usize) -> ::core::option::Option<Self> {
+                    <Self as ::core::convert::TryFrom<usize>>::try_from(ordinal).ok()
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    let try_from_repr = if repr == "usize" {
+        TokenStream::new()
+    } else {
+        quote! {
+            impl #impl_generics ::core::convert::TryFrom<#repr> for #enum_ident #ty_generics #where_clause {
+                type Error = ::ordinalizer::InvalidOrdinal;
+
+                fn try_from(ordinal: #repr) -> ::core::result::Result<Self, Self::Error> {
+                    Self::from_ordinal(ordinal)
+                        .ok_or(::ordinalizer::InvalidOrdinal::new(ordinal as usize))
+                }
+            }
+        }
+    };
+
+    quote! {
+        impl #impl_generics #enum_ident #ty_generics #where_clause {
+            /// Returns the variant with the given ordinal, or `None`
+            /// if no variant has that ordinal.
+            #fn_attrs
+            #vis #constness fn from_ordinal(ordinal: #repr) -> ::core::option::Option<Self> {
+                match ordinal {
+                    #(#arms,)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+
+        #from_ordinal_impl
+
+        impl #impl_generics ::core::convert::TryFrom<usize> for #enum_ident #ty_generics #where_clause {
+            type Error = ::ordinalizer::InvalidOrdinal;
+
+            fn try_from(ordinal: usize) -> ::core::result::Result<Self, Self::Error> {
+                <#repr as ::core::convert::TryFrom<usize>>::try_from(ordinal)
+                    .ok()
+                    .and_then(Self::from_ordinal)
+                    .ok_or(::ordinalizer::InvalidOrdinal::new(ordinal))
+            }
+        }
+
+        #try_from_repr
+    }
+}
+
+/// Generates the list of all variants of a fieldless enum.
+fn generate_all(
+    variants: &[Variant],
+    input: &DeriveInput,
+    vis: &syn::Visibility,
+    fn_attrs: &TokenStream,
+) -> TokenStream {
+    let enum_ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let count = variants.iter().filter(|v| v.ordinal.is_some()).count();
+    let values = in_ordinal_order(variants).map(|variant| {
+        let variant_ident = variant.ident;
+        let cfgs = &variant.cfgs;
+        quote! { #(#cfgs)* #enum_ident::#variant_ident }
+    });
+
+    quote! {
+        impl #impl_generics #enum_ident #ty_generics #where_clause {
+            /// Every variant of this enum, in ordinal order.
+            #vis const ALL: [Self; #count] = [#(#values),*];
+
+            /// Iterates over every variant of this enum, in ordinal order.
+            #fn_attrs
+            #vis fn all() -> impl ::core::iter::DoubleEndedIterator<Item = Self>
+                + ::core::iter::ExactSizeIterator
+                + ::core::iter::FusedIterator
+            {
+                ::core::iter::IntoIterator::into_iter(Self::ALL)
+            }
+        }
+    }
+}
+
+/// Generates `VARIANTS`, describing every variant in declaration
+/// order, and `variant_info()`, which looks up a value's entry.
+fn generate_variant_infos(
+    variants: &[Variant],
+    input: &DeriveInput,
+    vis: &syn::Visibility,
+    fn_attrs: &TokenStream,
+) -> TokenStream {
+    let enum_ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let infos = variants.iter().map(|variant| {
+        let name = variant_name(variant.ident);
+        let ordinal = match variant.ordinal {
+            Some(_) => {
+                let const_ident = ordinal_const_ident(variant.ident);
+                quote! { ::core::option::Option::Some(Self::#const_ident as usize) }
+            }
+            None => quote! { ::core::option::Option::None },
+        };
+        let style = match variant.fields {
+            syn::Fields::Unit => quote! { Unit },
+            syn::Fields::Unnamed(_) => quote! { Tuple },
+            syn::Fields::Named(_) => quote! { Named },
+        };
+        let field_count = variant.fields.len();
+        let field_names = variant
+            .fields
+            .iter()
+            .filter_map(|field| field.ident.as_ref().map(variant_name));
+        let cfgs = &variant.cfgs;
+        quote! {
+            #(#cfgs)*
+            ::ordinalizer::VariantInfo::new(
+                #name,
+                #ordinal,
+                ::ordinalizer::FieldStyle::#style,
+                #field_count,
+                &[#(#field_names),*],
+            )
+        }
+    });
+
+    let arms = variants.iter().enumerate().map(|(i, variant)| {
+        let pattern = variant_pattern(enum_ident, variant);
+        let cfgs = &variant.cfgs;
+        quote! {
+            #(#cfgs)*
+            #pattern => &Self::VARIANTS[#i]
+        }
+    });
+
+    quote! {
+        impl #impl_generics #enum_ident #ty_generics #where_clause {
+            /// Descriptions of this enum's variants, in declaration order.
+            #vis const VARIANTS: &'static [::ordinalizer::VariantInfo] = &[#(#infos),*];
+
+            /// Returns the description of this value's variant.
+            #fn_attrs
+            #vis const fn variant_info(&self) -> &'static ::ordinalizer::VariantInfo {
+                match *self {
+                    #(#arms,)*
+                }
+            }
+        }
+    }
+}
+
+/// Generates methods stepping through the variants of a
+/// fieldless enum with at least one numbered variant.
+fn generate_navigation(
+    variants: &[Variant],
+    input: &DeriveInput,
+    vis: &syn::Visibility,
+    fn_attrs: &TokenStream,
+) -> TokenStream {
+    let enum_ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let ordered: Vec<_> = in_ordinal_order(variants).collect();
+    let first = ordered[0].ident;
+    let last = ordered[ordered.len() - 1].ident;
+
+    let mut next_arms = Vec::new();
+    let mut prev_arms = Vec::new();
+    let mut wrapping_next_arms = Vec::new();
+    let mut wrapping_prev_arms = Vec::new();
+    for (i, variant) in ordered.iter().enumerate() {
+        let variant_ident = variant.ident;
+        let cfgs = &variant.cfgs;
+        let pattern = quote! { #(#cfgs)* #enum_ident::#variant_ident };
+        let next = ordered.get(i + 1).map(|next| next.ident);
+        let prev = i.checked_sub(1).map(|prev| ordered[prev].ident);
+
+        next_arms.push(match next {
+            Some(next) => quote! { #pattern => ::core::option::Option::Some(#enum_ident::#next) },
+            None => quote! { #pattern => ::core::option::Option::None },
+        });
+        prev_arms.push(match prev {
+            Some(prev) => quote! { #pattern => ::core::option::Option::Some(#enum_ident::#prev) },
+            None => quote! { #pattern => ::core::option::Option::None },
+        });
+        let wrapping_next = next.unwrap_or(first);
+        let wrapping_prev = prev.unwrap_or(last);
+        wrapping_next_arms.push(quote! { #pattern => #enum_ident::#wrapping_next });
+        wrapping_prev_arms.push(quote! { #pattern => #enum_ident::#wrapping_prev });
+    }
+    for variant in variants.iter().filter(|v| v.ordinal.is_none()) {
+        let pattern = variant_pattern(enum_ident, variant);
+        let cfgs = &variant.cfgs;
+        let pattern = quote! { #(#cfgs)* #pattern };
+        next_arms.push(quote! { #pattern => ::core::option::Option::None });
+        prev_arms.push(quote! { #pattern => ::core::option::Option::None });
+        wrapping_next_arms.push(quote! { #pattern => #enum_ident::#first });
+        wrapping_prev_arms.push(quote! { #pattern => #enum_ident::#last });
+    }
+
+    // The wrapping methods match on `self` rather than on `next()`
+    // and `prev()`, since a const fn cannot drop an `Option<Self>`
+    // if `Self` has fields with destructors.
+    quote! {
+        impl #impl_generics #enum_ident #ty_generics #where_clause {
+            /// Returns the variant with the lowest ordinal.
+            #fn_attrs
+            #vis const fn first() -> Self {
+                #enum_ident::#first
+            }
+
+            /// Returns the variant with the highest ordinal.
+            #fn_attrs
+            #vis const fn last() -> Self {
+                #enum_ident::#last
+            }
+
+            /// Returns the variant following this one in ordinal
+            /// order, or `None` if this is the last variant.
+            #fn_attrs
+            #vis const fn next(&self) -> ::core::option::Option<Self> {
+                match *self {
+                    #(#next_arms,)*
+                }
+            }
+
+            /// Returns the variant preceding this one in ordinal
+            /// order, or `None` if this is the first variant.
+            #fn_attrs
+            #vis const fn prev(&self) -> ::core::option::Option<Self> {
+                match *self {
+                    #(#prev_arms,)*
+                }
+            }
+
+            /// Returns the variant following this one in ordinal
+            /// order, wrapping around to the first variant.
+            #fn_attrs
+            #vis const fn wrapping_next(&self) -> Self {
+                match *self {
+                    #(#wrapping_next_arms,)*
+                }
+            }
+
+            /// Returns the variant preceding this one in ordinal
+            /// order, wrapping around to the last variant.
+            #fn_attrs
+            #vis const fn wrapping_prev(&self) -> Self {
+                match *self {
+                    #(#wrapping_prev_arms,)*
+                }
+            }
+        }
+    }
+}
+
+/// Generates `Serialize` and `Deserialize` impls which
+/// represent variants by their ordinals.
+fn generate_serde(
+    span: Span,
+    method: &Ident,
+    variants: &[Variant],
+    input: &DeriveInput,
+    repr: &Ident,
+    on_skip: &OnSkip,
+    variant_count: usize,
+) -> TokenStream {
+    if cfg!(not(feature = "serde")) {
+        emit_error!(
+            span,
+            "`#[ordinal(serde)]` requires the `serde` feature of `ordinalizer`"
+        );
+        return TokenStream::new();
+    }
+    let has_skipped = variants.iter().any(|v| v.ordinal.is_none());
+    match *on_skip {
+        OnSkip::None if has_skipped => {
+            emit_error!(
+                span,
+                "`#[ordinal(serde)]` requires every variant to have an ordinal";
+                help = "set `on_skip` to `panic`"
+            );
+            return TokenStream::new();
+        }
+        OnSkip::Fallback(_, fallback_span) if has_skipped => {
+            emit_error!(
+                fallback_span,
+                "`#[ordinal(serde)]` cannot be combined with a fallback ordinal, \
+                 since skipped variants would deserialize as another variant";
+                help = "set `on_skip` to `panic`"
+            );
+            return TokenStream::new();
+        }
+        _ => {}
+    }
+    let mut has_fields = false;
+    for variant in variants.iter().filter(|v| !v.fields.is_empty()) {
+        emit_error!(
+            variant.ident,
+            "`#[ordinal(serde)]` does not support variants with fields"
+        );
+        has_fields = true;
+    }
+    if has_fields {
+        return TokenStream::new();
+    }
+
+    let enum_ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut de_generics = input.generics.clone();
+    de_generics.params.insert(0, parse_quote! { 'de });
+    let (de_impl_generics, _, _) = de_generics.split_for_impl();
+
+    let expected = format!("an ordinal of `{}` below {}", enum_ident, variant_count);
+
+    quote! {
+        impl #impl_generics ::ordinalizer::__private::serde::Serialize for #enum_ident #ty_generics #where_clause {
+            fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+            where
+                S: ::ordinalizer::__private::serde::Serializer,
+            {
+                ::ordinalizer::__private::serde::Serialize::serialize(&Self::#method(self), serializer)
+            }
+        }
+
+        impl #de_impl_generics ::ordinalizer::__private::serde::Deserialize<'de> for #enum_ident #ty_generics #where_clause {
+            fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error>
+            where
+                D: ::ordinalizer::__private::serde::Deserializer<'de>,
+            {
+                let ordinal: #repr = ::ordinalizer::__private::serde::Deserialize::deserialize(deserializer)?;
+                Self::from_ordinal(ordinal).ok_or_else(|| {
+                    ::ordinalizer::__private::serde::de::Error::invalid_value(
+                        ::ordinalizer::__private::serde::de::Unexpected::Unsigned(ordinal as u64),
+                        &#expected,
+                    )
+                })
+            }
+        }
+    }
+}
+
+/// Generates impls of the comparison traits which order
+/// values by the ordinals of their variants.
+fn generate_ord(
+    ord_by: OrdBy,
+    span: Span,
+    method: &Ident,
+    variants: &[Variant],
+    input: &DeriveInput,
+    on_skip: &OnSkip,
+) -> TokenStream {
+    let has_skipped = variants.iter().any(|v| v.ordinal.is_none());
+    match *on_skip {
+        OnSkip::None if has_skipped => {
+            emit_error!(
+                span,
+                "`#[ordinal(ord)]` requires every variant to have an ordinal";
+                help = "set `on_skip` to `panic` or a fallback ordinal"
+            );
+            return TokenStream::new();
+        }
+        // A skipped variant would equal the variant with the fallback
+        // ordinal, while two values of the skipped variant could still
+        // differ by their fields, which breaks transitivity.
+        OnSkip::Fallback(_, fallback_span) if has_skipped && ord_by == OrdBy::Fields => {
+            emit_error!(
+                fallback_span,
+                "`#[ordinal(ord = \"fields\")]` cannot be combined with a fallback ordinal";
+                help = "set `on_skip` to `panic`, or use `#[ordinal(ord)]`"
+            );
+            return TokenStream::new();
+        }
+        _ => {}
+    }
+
+    let enum_ident = &input.ident;
+
+    // Ties between values of the same variant are broken by
+    // comparing their fields in order, which requires the
+    // fields to be `Ord`.
+    let mut generics = input.generics.clone();
+    let mut tie_arms = Vec::new();
+    if ord_by == OrdBy::Fields {
+        let where_clause = generics.make_where_clause();
+        for variant in variants {
+            let variant_ident = variant.ident;
+            let members: Vec<_> = variant
+                .fields
+                .iter()
+                .enumerate()
+                .map(|(i, field)| field_member(field, i))
+                .collect();
+            let lhs: Vec<_> = (0..members.len())
+                .map(|i| Ident::new(&format!("lhs_{}", i), Span::call_site()))
+                .collect();
+            let rhs: Vec<_> = (0..members.len())
+                .map(|i| Ident::new(&format!("rhs_{}", i), Span::call_site()))
+                .collect();
+            for field in variant.fields {
+                let ty = &field.ty;
+                where_clause
+                    .predicates
+                    .push(parse_quote_spanned! { ty.span()=> #ty: ::core::cmp::Ord });
+            }
+
+            let cfgs = &variant.cfgs;
+            tie_arms.push(quote! {
+                #(#cfgs)*
+                (
+                    #enum_ident::#variant_ident { #(#members: #lhs),* },
+                    #enum_ident::#variant_ident { #(#members: #rhs),* },
+                ) => ::core::cmp::Ordering::Equal
+                    #(.then_with(|| ::core::cmp::Ord::cmp(#lhs, #rhs)))*
+            });
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics ::core::cmp::PartialEq for #enum_ident #ty_generics #where_clause {
+            fn eq(&self, other: &Self) -> bool {
+                ::core::cmp::Ord::cmp(self, other) == ::core::cmp::Ordering::Equal
+            }
+        }
+
+        impl #impl_generics ::core::cmp::Eq for #enum_ident #ty_generics #where_clause {}
+
+        impl #impl_generics ::core::cmp::PartialOrd for #enum_ident #ty_generics #where_clause {
+            fn partial_cmp(&self, other: &Self) -> ::core::option::Option<::core::cmp::Ordering> {
+                ::core::option::Option::Some(::core::cmp::Ord::cmp(self, other))
+            }
+        }
+
+        impl #impl_generics ::core::cmp::Ord for #enum_ident #ty_generics #where_clause {
+            fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+                #[allow(unreachable_patterns)]
+                match (self, other) {
+                    #(#tie_arms,)*
+                    _ => ::core::cmp::Ord::cmp(&Self::#method(self), &Self::#method(other)),
+                }
+            }
+        }
+    }
+}
+
+fn generate_kind(
+    kind_ident: &Ident,
+    variants: &[Variant],
+    input: &DeriveInput,
+    repr: &Ident,
+    attrs: &EnumAttrs,
+    fn_attrs: &TokenStream,
+) -> TokenStream {
+    let enum_ident = &input.ident;
+    let vis = &input.vis;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // Skipped variants have no place in the ordinal order,
+    // so they come last.
+    let skipped = variants.iter().filter(|v| v.ordinal.is_none());
+    let kind_variants = in_ordinal_order(variants).chain(skipped).map(|variant| {
+        let variant_ident = variant.ident;
+        let index = match variant.ordinal {
+            Some(ordinal) => {
+                let ordinal = Literal::usize_unsuffixed(ordinal);
+                quote! { index = #ordinal }
+            }
+            None => quote! { skip },
+        };
+        let docs = variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("doc"));
+        let cfgs = &variant.cfgs;
+        quote! {
+            #(#docs)*
+            #(#cfgs)*
+            #[ordinal(#index)]
+            #variant_ident
+        }
+    });
+
+    let arms = variants.iter().map(|variant| {
+        let variant_ident = variant.ident;
+        let pattern = variant_pattern(enum_ident, variant);
+        let cfgs = &variant.cfgs;
+        quote! {
+            #(#cfgs)*
+            #pattern => #kind_ident::#variant_ident
+        }
+    });
+
+    let doc = format!("The variants of [`{}`], without their fields.", enum_ident);
+    let repr = repr.to_string();
+    let stable_cfg = if attrs.stable_cfg {
+        quote! { #[ordinal(stable_cfg)] }
+    } else {
+        TokenStream::new()
+    };
+    let on_skip = match attrs.on_skip {
+        OnSkip::None => quote! { "none" },
+        OnSkip::Panic => quote! { "panic" },
+        OnSkip::Fallback(fallback, _) => {
+            let fallback = Literal::usize_unsuffixed(fallback);
+            quote! { #fallback }
+        }
+    };
+    let mut options = Vec::new();
+    if let Some(method) = &attrs.method {
+        let method = method.to_string();
+        options.push(quote! { method = #method });
+    }
+    if let Some(kind_vis) = &attrs.vis {
+        let kind_vis = quote!(#kind_vis).to_string();
+        options.push(quote! { vis = #kind_vis });
+    }
+    if !attrs.attrs.is_empty() {
+        let method_attrs = &attrs.attrs;
+        options.push(quote! { attrs(#(#method_attrs),*) });
+    }
+    let item_vis = match &attrs.vis {
+        Some(item_vis) => quote! { #item_vis },
+        None => quote! { pub },
+    };
+
+    quote! {
+        #[doc = #doc]
+        #[derive(
+            ::core::fmt::Debug,
+            ::core::clone::Clone,
+            ::core::marker::Copy,
+            ::core::cmp::PartialEq,
+            ::core::cmp::Eq,
+            ::core::hash::Hash,
+            ::core::cmp::PartialOrd,
+            ::core::cmp::Ord,
+            ::ordinalizer::Ordinal,
+        )]
+        #[ordinal(repr = #repr, on_skip = #on_skip #(, #options)*)]
+        #stable_cfg
+        #vis enum #kind_ident {
+            #(#kind_variants,)*
+        }
+
+        impl #impl_generics #enum_ident #ty_generics #where_clause {
+            /// Returns the kind of this value's variant.
+            #fn_attrs
+            #item_vis const fn kind(&self) -> #kind_ident {
+                match *self {
+                    #(#arms,)*
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,320 @@
+//! Checking ordinals against a lockfile, for `#[ordinal(lock = "...")]`.
+//!
+//! A lockfile holds one section per enum, listing the
+//! ordinal of each of its variants:
+//!
+//! ```text
+//! [Message]
+//! Ping = 0
+//! Chat = 1
+//! ```
+
+use ordinalizer_analysis::{variant_name, Variant};
+use proc_macro2::{Ident, TokenStream};
+use proc_macro_error::*;
+use quote::quote;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::{env, fs};
+
+/// Setting this environment variable makes the derive write the
+/// current ordinals to the lockfile instead of checking them.
+const UPDATE_VAR: &str = "ORDINALIZER_UPDATE_LOCK";
+
+const HEADER: &str = "\
+# Ordinals of enums deriving `Ordinal` with `#[ordinal(lock = ...)]`.
+# Build with ORDINALIZER_UPDATE_LOCK=1 set to update this file.
+";
+
+struct Section {
+    name: String,
+    entries: Vec<(String, usize)>,
+}
+
+/// Checks the ordinals of the variants against the lockfile,
+/// or updates the lockfile if `ORDINALIZER_UPDATE_LOCK` is set.
+///
+/// With `stable_cfg`, a locked variant may be missing because it is
+/// cfg'd out, so it only counts as removed if its ordinal was taken.
+///
+/// The returned tokens make the compiler track the lockfile and
+/// the environment variable, so that the derive runs again when
+/// either of them changes.
+pub fn check_lock(
+    lock: &syn::LitStr,
+    enum_ident: &Ident,
+    variants: &[Variant],
+    stable_cfg: bool,
+) -> TokenStream {
+    if let Some(flattened) = variants.iter().find(|v| v.flatten.is_some()) {
+        emit_error!(
+            lock,
+            "`lock` cannot be combined with `#[ordinal(flatten)]` on `{}`",
+            flattened.ident
+        );
+        return TokenStream::new();
+    }
+
+    let manifest_dir = env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let path = PathBuf::from(manifest_dir).join(lock.value());
+    let name = enum_ident.to_string();
+    let mut current: Vec<(String, usize)> = variants
+        .iter()
+        .filter_map(|v| Some((variant_name(v.ident), v.ordinal?)))
+        .collect();
+    current.sort_by_key(|&(_, ordinal)| ordinal);
+
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => Some(contents),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+        Err(err) => abort!(lock, "failed to read `{}`: {}", path.display(), err),
+    };
+    let mut sections = match &contents {
+        Some(contents) => parse(contents).unwrap_or_else(|(line, message)| {
+            abort!(lock, "{}:{}: {}", lock.value(), line, message)
+        }),
+        None => Vec::new(),
+    };
+
+    if env::var_os(UPDATE_VAR).is_some() {
+        match sections.iter_mut().find(|section| section.name == name) {
+            Some(section) => section.entries = current,
+            None => sections.push(Section {
+                name,
+                entries: current,
+            }),
+        }
+        if let Err(err) = fs::write(&path, render(&sections)) {
+            abort!(lock, "failed to write `{}`: {}", path.display(), err);
+        }
+    } else {
+        let locked = match sections.iter().find(|section| section.name == name) {
+            Some(section) => &section.entries,
+            None => abort!(
+                lock,
+                "`{}` has no ordinals for `{}`",
+                lock.value(),
+                name;
+                help = "build with `{}=1` set to add them", UPDATE_VAR
+            ),
+        };
+        let diff = diff(locked, &current, stable_cfg);
+        if !diff.is_empty() {
+            emit_error!(
+                lock,
+                "the ordinals of `{}` changed since they were locked in `{}`:\n{}",
+                name,
+                lock.value(),
+                diff;
+                help = "if the change is intended, build with `{}=1` set to update the lockfile", UPDATE_VAR
+            );
+        }
+    }
+
+    let path = path.to_string_lossy();
+    quote! {
+        const _: &[u8] = ::core::include_bytes!(#path);
+        const _: ::core::option::Option<&str> = ::core::option_env!(#UPDATE_VAR);
+    }
+}
+
+/// Returns a diff of the locked entries which changed or were
+/// removed, along with the entries which now have their ordinals.
+/// Variants which were added with new ordinals do not show up in
+/// the diff, nor, with `stable_cfg`, do missing variants whose
+/// ordinals are unused.
+fn diff(locked: &[(String, usize)], current: &[(String, usize)], stable_cfg: bool) -> String {
+    let mut diff = String::new();
+
+    for (name, ordinal) in locked {
+        if current.contains(&(name.clone(), *ordinal)) {
+            continue;
+        }
+        let moved = current.iter().find(|(other, _)| other == name);
+        let replaced = current.iter().find(|(_, other)| other == ordinal);
+        if stable_cfg && moved.is_none() && replaced.is_none() {
+            continue;
+        }
+
+        let _ = writeln!(diff, "  - {} = {}", name, ordinal);
+        if let Some((name, ordinal)) = moved {
+            let _ = writeln!(diff, "  + {} = {}", name, ordinal);
+        }
+        if let Some((other, ordinal)) = replaced {
+            if !locked.iter().any(|(name, _)| name == other) {
+                let _ = writeln!(diff, "  + {} = {}", other, ordinal);
+            }
+        }
+    }
+
+    diff
+}
+
+fn parse(contents: &str) -> Result<Vec<Section>, (usize, String)> {
+    let mut sections: Vec<Section> = Vec::new();
+
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            sections.push(Section {
+                name: name.trim().to_owned(),
+                entries: Vec::new(),
+            });
+            continue;
+        }
+
+        let section = sections
+            .last_mut()
+            .ok_or_else(|| (i + 1, "expected a `[EnumName]` section".to_owned()))?;
+        let (name, ordinal) = line
+            .split_once('=')
+            .ok_or_else(|| (i + 1, "expected `Variant = ordinal`".to_owned()))?;
+        let ordinal = ordinal
+            .trim()
+            .parse()
+            .map_err(|_| (i + 1, format!("invalid ordinal `{}`", ordinal.trim())))?;
+        section.entries.push((name.trim().to_owned(), ordinal));
+    }
+
+    Ok(sections)
+}
+
+fn render(sections: &[Section]) -> String {
+    let mut contents = String::from(HEADER);
+    for section in sections {
+        let _ = write!(contents, "\n[{}]\n", section.name);
+        for (name, ordinal) in &section.entries {
+            let _ = writeln!(contents, "{} = {}", name, ordinal);
+        }
+    }
+    contents
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(entries: &[(&str, usize)]) -> Vec<(String, usize)> {
+        entries
+            .iter()
+            .map(|&(name, ordinal)| (name.to_owned(), ordinal))
+            .collect()
+    }
+
+    #[test]
+    fn diff_unchanged() {
+        let locked = entries(&[("A", 0), ("B", 1)]);
+        assert_eq!(diff(&locked, &locked, false), "");
+    }
+
+    #[test]
+    fn diff_append() {
+        let locked = entries(&[("A", 0), ("B", 1)]);
+        let current = entries(&[("A", 0), ("B", 1), ("C", 2)]);
+        assert_eq!(diff(&locked, &current, false), "");
+    }
+
+    #[test]
+    fn diff_reorder() {
+        let locked = entries(&[("A", 0), ("B", 1)]);
+        let current = entries(&[("B", 0), ("A", 1)]);
+        assert_eq!(
+            diff(&locked, &current, false),
+            "  - A = 0\n  + A = 1\n  - B = 1\n  + B = 0\n"
+        );
+    }
+
+    #[test]
+    fn diff_insert() {
+        let locked = entries(&[("A", 0), ("B", 1)]);
+        let current = entries(&[("A", 0), ("New", 1), ("B", 2)]);
+        assert_eq!(
+            diff(&locked, &current, false),
+            "  - B = 1\n  + B = 2\n  + New = 1\n"
+        );
+    }
+
+    #[test]
+    fn diff_removal() {
+        let locked = entries(&[("A", 0), ("B", 1), ("C", 2)]);
+        let current = entries(&[("A", 0), ("C", 2)]);
+        assert_eq!(diff(&locked, &current, false), "  - B = 1\n");
+    }
+
+    #[test]
+    fn diff_rename() {
+        let locked = entries(&[("A", 0), ("B", 1)]);
+        let current = entries(&[("A", 0), ("Bee", 1)]);
+        assert_eq!(diff(&locked, &current, false), "  - B = 1\n  + Bee = 1\n");
+    }
+
+    #[test]
+    fn diff_stable_cfg() {
+        // `B` may be cfg'd out, as long as its ordinal stays unused.
+        let locked = entries(&[("A", 0), ("B", 1), ("C", 2)]);
+        let current = entries(&[("A", 0), ("C", 2)]);
+        assert_eq!(diff(&locked, &current, true), "");
+
+        let current = entries(&[("A", 0), ("D", 1), ("C", 2)]);
+        assert_eq!(diff(&locked, &current, true), "  - B = 1\n  + D = 1\n");
+
+        let current = entries(&[("A", 0), ("C", 1)]);
+        assert_eq!(
+            diff(&locked, &current, true),
+            "  - B = 1\n  - C = 2\n  + C = 1\n"
+        );
+    }
+
+    #[test]
+    fn parse_sections() {
+        let sections = parse(
+            "# comment\n\
+             \n\
+             [Message]\n\
+             Ping = 0\n\
+             \x20 Chat=1 \n\
+             \n\
+             [ Status ]\n\
+             Active = 3\n",
+        )
+        .unwrap();
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].name, "Message");
+        assert_eq!(sections[0].entries, entries(&[("Ping", 0), ("Chat", 1)]));
+        assert_eq!(sections[1].name, "Status");
+        assert_eq!(sections[1].entries, entries(&[("Active", 3)]));
+    }
+
+    #[test]
+    fn parse_render() {
+        let sections = [Section {
+            name: "Message".to_owned(),
+            entries: entries(&[("Ping", 0), ("Chat", 1)]),
+        }];
+        let parsed = parse(&render(&sections)).unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].name, "Message");
+        assert_eq!(parsed[0].entries, sections[0].entries);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = |contents| parse(contents).err().unwrap();
+        assert_eq!(
+            error("Ping = 0\n"),
+            (1, "expected a `[EnumName]` section".to_owned())
+        );
+        assert_eq!(
+            error("[Message]\nPing 0\n"),
+            (2, "expected `Variant = ordinal`".to_owned())
+        );
+        assert_eq!(
+            error("[Message]\n\nPing = first\n"),
+            (3, "invalid ordinal `first`".to_owned())
+        );
+    }
+}
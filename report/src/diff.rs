@@ -0,0 +1,285 @@
+//! Comparing the ordinals of two scanned source trees.
+
+use crate::scan::EnumReport;
+use std::fmt::Write as _;
+use std::fmt::{self, Display};
+
+/// A change to the ordinals of an enum.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    /// The enum was added.
+    EnumAdded,
+    /// The enum was removed, or no longer derives `Ordinal`.
+    EnumRemoved,
+    /// A variant was added with an ordinal which was not used before.
+    Added { name: String, ordinal: usize },
+    /// A flattened variant gained ordinals at its end.
+    Grown {
+        name: String,
+        old: usize,
+        new: usize,
+    },
+    /// A variant kept its name but changed its ordinal.
+    Reordered {
+        name: String,
+        old: usize,
+        new: usize,
+    },
+    /// A variant was removed or skipped, and its ordinal is unused.
+    Removed { name: String, ordinal: usize },
+    /// A variant was removed or skipped, and a new variant took its ordinal.
+    Renamed {
+        old: String,
+        new: String,
+        ordinal: usize,
+    },
+    /// A flattened variant lost ordinals at its end.
+    Shrunk {
+        name: String,
+        old: usize,
+        new: usize,
+    },
+}
+
+impl Change {
+    /// Returns whether values persisted before the change
+    /// may be read back as a different variant after it.
+    pub fn is_breaking(&self) -> bool {
+        match self {
+            Change::EnumAdded | Change::Added { .. } | Change::Grown { .. } => false,
+            Change::EnumRemoved
+            | Change::Reordered { .. }
+            | Change::Removed { .. }
+            | Change::Renamed { .. }
+            | Change::Shrunk { .. } => true,
+        }
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Change::EnumAdded => write!(f, "enum added"),
+            Change::EnumRemoved => write!(f, "enum removed"),
+            Change::Added { name, ordinal } => write!(f, "added `{}` = {}", name, ordinal),
+            Change::Grown { name, old, new } => {
+                write!(f, "grown `{}` from {} to {} ordinals", name, old, new)
+            }
+            Change::Reordered { name, old, new } => {
+                write!(f, "reordered `{}` from {} to {}", name, old, new)
+            }
+            Change::Removed { name, ordinal } => write!(f, "removed `{}` = {}", name, ordinal),
+            Change::Renamed { old, new, ordinal } => {
+                write!(f, "renamed `{}` to `{}` = {}", old, new, ordinal)
+            }
+            Change::Shrunk { name, old, new } => {
+                write!(f, "shrunk `{}` from {} to {} ordinals", name, old, new)
+            }
+        }
+    }
+}
+
+/// The changes to one enum.
+pub struct EnumDiff<'a> {
+    /// The enum in the new tree, or in the old tree if it was removed.
+    pub report: &'a EnumReport,
+    pub changes: Vec<Change>,
+}
+
+impl EnumDiff<'_> {
+    pub fn is_breaking(&self) -> bool {
+        self.changes.iter().any(Change::is_breaking)
+    }
+}
+
+/// The result of comparing two trees.
+#[derive(Default)]
+pub struct Diff<'a> {
+    /// The enums whose ordinals changed, in the order of the new
+    /// tree, followed by the enums which were removed.
+    pub enums: Vec<EnumDiff<'a>>,
+    /// The enums in the new tree whose ordinals could not be
+    /// compared, because of errors or unresolved flattened enums.
+    pub unknown: Vec<&'a EnumReport>,
+}
+
+/// Compares the ordinals of the enums in `old` and `new`.
+///
+/// Enums are matched by their file and their path within it, or
+/// by their path alone if it is unique in both trees, so that
+/// moving an enum to another file is not reported as a change.
+pub fn diff<'a>(old: &'a [EnumReport], new: &'a [EnumReport]) -> Diff<'a> {
+    let mut old_left: Vec<&EnumReport> = old.iter().collect();
+    let mut new_left: Vec<&EnumReport> = Vec::new();
+    let mut pairs = Vec::new();
+
+    for report in new {
+        match old_left
+            .iter()
+            .position(|other| other.file == report.file && other.name == report.name)
+        {
+            Some(i) => pairs.push((old_left.remove(i), report)),
+            None => new_left.push(report),
+        }
+    }
+
+    let mut i = 0;
+    while i < new_left.len() {
+        let name = &new_left[i].name;
+        let new_count = new_left.iter().filter(|r| r.name == *name).count();
+        let old_matches: Vec<usize> = (0..old_left.len())
+            .filter(|&j| old_left[j].name == *name)
+            .collect();
+        if let ([j], 1) = (&old_matches[..], new_count) {
+            pairs.push((old_left.remove(*j), new_left.remove(i)));
+        } else {
+            i += 1;
+        }
+    }
+    // Keep the order of the new tree.
+    pairs.sort_by_key(|(_, report)| position(new, report));
+
+    let mut diff = Diff::default();
+    for (old_report, new_report) in pairs {
+        match (ordinals(old_report), ordinals(new_report)) {
+            (Some(old), Some(new)) => {
+                let changes = diff_variants(&old, &new);
+                if !changes.is_empty() {
+                    diff.enums.push(EnumDiff {
+                        report: new_report,
+                        changes,
+                    });
+                }
+            }
+            _ => diff.unknown.push(new_report),
+        }
+    }
+    for report in new_left {
+        let i = diff
+            .enums
+            .iter()
+            .position(|d| position(new, d.report) > position(new, report))
+            .unwrap_or(diff.enums.len());
+        diff.enums.insert(
+            i,
+            EnumDiff {
+                report,
+                changes: vec![Change::EnumAdded],
+            },
+        );
+    }
+    diff.enums
+        .extend(old_left.into_iter().map(|report| EnumDiff {
+            report,
+            changes: vec![Change::EnumRemoved],
+        }));
+
+    diff
+}
+
+fn position(reports: &[EnumReport], report: &EnumReport) -> usize {
+    reports
+        .iter()
+        .position(|other| std::ptr::eq(other, report))
+        .unwrap_or(usize::MAX)
+}
+
+/// Returns the name, first ordinal and ordinal count of each
+/// numbered variant, or `None` if any of them are unknown.
+fn ordinals(report: &EnumReport) -> Option<Vec<(&str, usize, usize)>> {
+    report
+        .variants
+        .as_ref()
+        .ok()?
+        .iter()
+        .filter(|variant| !variant.skipped)
+        .map(|variant| Some((variant.name.as_str(), variant.ordinal?, variant.count?)))
+        .collect()
+}
+
+fn diff_variants(old: &[(&str, usize, usize)], new: &[(&str, usize, usize)]) -> Vec<Change> {
+    let mut changes = Vec::new();
+    let in_old = |name: &str| old.iter().any(|&(other, _, _)| other == name);
+    let in_new = |name: &str| new.iter().any(|&(other, _, _)| other == name);
+
+    for &(name, ordinal, count) in old {
+        match new.iter().find(|&&(other, _, _)| other == name) {
+            Some(&(_, new_ordinal, new_count)) => {
+                if new_ordinal != ordinal {
+                    changes.push(Change::Reordered {
+                        name: name.to_owned(),
+                        old: ordinal,
+                        new: new_ordinal,
+                    });
+                } else if new_count > count {
+                    changes.push(Change::Grown {
+                        name: name.to_owned(),
+                        old: count,
+                        new: new_count,
+                    });
+                } else if new_count < count {
+                    changes.push(Change::Shrunk {
+                        name: name.to_owned(),
+                        old: count,
+                        new: new_count,
+                    });
+                }
+            }
+            None => match new
+                .iter()
+                .find(|&&(other, new_ordinal, _)| new_ordinal == ordinal && !in_old(other))
+            {
+                Some(&(other, _, _)) => changes.push(Change::Renamed {
+                    old: name.to_owned(),
+                    new: other.to_owned(),
+                    ordinal,
+                }),
+                None => changes.push(Change::Removed {
+                    name: name.to_owned(),
+                    ordinal,
+                }),
+            },
+        }
+    }
+
+    for &(name, ordinal, _) in new {
+        let renamed = old
+            .iter()
+            .any(|&(other, old_ordinal, _)| old_ordinal == ordinal && !in_new(other));
+        if !in_old(name) && !renamed {
+            changes.push(Change::Added {
+                name: name.to_owned(),
+                ordinal,
+            });
+        }
+    }
+
+    changes
+}
+
+/// Renders the changes as text, one enum per paragraph.
+pub fn render(diff: &Diff) -> String {
+    let mut out = String::new();
+    for enum_diff in &diff.enums {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        let report = enum_diff.report;
+        let _ = writeln!(
+            out,
+            "{} ({}:{}), {}",
+            report.name,
+            report.file.display(),
+            report.line,
+            if enum_diff.is_breaking() {
+                "breaking"
+            } else {
+                "safe"
+            }
+        );
+        for change in &enum_diff.changes {
+            let _ = writeln!(out, "  {}", change);
+        }
+    }
+    out
+}
@@ -0,0 +1,167 @@
+//! Rendering scanned enums as text, JSON or Markdown.
+
+use crate::scan::{EnumReport, VariantReport};
+use serde_json::json;
+use std::fmt::Write as _;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Markdown,
+}
+
+impl Format {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            "markdown" | "md" => Some(Format::Markdown),
+            _ => None,
+        }
+    }
+
+    pub fn render(self, enums: &[EnumReport]) -> String {
+        match self {
+            Format::Text => text(enums),
+            Format::Json => json(enums),
+            Format::Markdown => markdown(enums),
+        }
+    }
+}
+
+/// Returns the ordinal column of a variant, e.g. `3`, `3..=5`,
+/// `skipped` or `?` if it depends on an enum which was not found.
+fn ordinals(variant: &VariantReport) -> String {
+    if variant.skipped {
+        return "skipped".to_owned();
+    }
+    match (variant.ordinal, variant.count) {
+        (Some(ordinal), Some(1)) if variant.flatten.is_none() => ordinal.to_string(),
+        (Some(ordinal), Some(0)) => format!("{}..{}", ordinal, ordinal),
+        (Some(ordinal), Some(count)) => format!("{}..={}", ordinal, ordinal + count - 1),
+        (Some(ordinal), None) => format!("{}..?", ordinal),
+        (None, _) => "?".to_owned(),
+    }
+}
+
+/// Returns the notes on a variant, e.g. `flatten(Inner), cfg`.
+fn notes(variant: &VariantReport) -> String {
+    let mut notes = Vec::new();
+    if let Some(ty) = &variant.flatten {
+        notes.push(format!("flatten({})", ty));
+    }
+    if variant.cfg {
+        notes.push("cfg".to_owned());
+    }
+    notes.join(", ")
+}
+
+fn count(report: &EnumReport) -> String {
+    match report.variant_count() {
+        Some(count) => count.to_string(),
+        None => "?".to_owned(),
+    }
+}
+
+fn text(enums: &[EnumReport]) -> String {
+    let mut out = String::new();
+    for report in enums {
+        let variants = match &report.variants {
+            Ok(variants) => variants,
+            Err(_) => continue,
+        };
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        let _ = writeln!(
+            out,
+            "{} ({}:{}), {} ordinals",
+            report.name,
+            report.file.display(),
+            report.line,
+            count(report)
+        );
+
+        let width = variants.iter().map(|v| v.name.len()).max().unwrap_or(0);
+        for variant in variants {
+            let line = format!(
+                "  {:width$}  {:>7}  {}",
+                variant.name,
+                ordinals(variant),
+                notes(variant),
+                width = width
+            );
+            let _ = writeln!(out, "{}", line.trim_end());
+        }
+    }
+    out
+}
+
+fn json(enums: &[EnumReport]) -> String {
+    let enums: Vec<_> = enums
+        .iter()
+        .filter_map(|report| {
+            let variants: Vec<_> = report
+                .variants
+                .as_ref()
+                .ok()?
+                .iter()
+                .map(|variant| {
+                    json!({
+                        "name": variant.name,
+                        "ordinal": variant.ordinal,
+                        "count": variant.count,
+                        "skipped": variant.skipped,
+                        "flatten": variant.flatten,
+                        "cfg": variant.cfg,
+                    })
+                })
+                .collect();
+            Some(json!({
+                "name": report.name,
+                "file": report.file.to_string_lossy().replace('\\', "/"),
+                "line": report.line,
+                "variant_count": report.variant_count(),
+                "variants": variants,
+            }))
+        })
+        .collect();
+
+    let mut out = serde_json::to_string_pretty(&json!({ "enums": enums })).unwrap();
+    out.push('\n');
+    out
+}
+
+fn markdown(enums: &[EnumReport]) -> String {
+    let mut out = String::new();
+    for report in enums {
+        let variants = match &report.variants {
+            Ok(variants) => variants,
+            Err(_) => continue,
+        };
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        let _ = writeln!(
+            out,
+            "### `{}`\n\n`{}:{}`, {} ordinals\n",
+            report.name,
+            report.file.display(),
+            report.line,
+            count(report)
+        );
+        let _ = writeln!(out, "| Variant | Ordinal | Notes |");
+        let _ = writeln!(out, "| --- | --- | --- |");
+        for variant in variants {
+            let _ = writeln!(
+                out,
+                "| `{}` | {} | {} |",
+                variant.name,
+                ordinals(variant),
+                notes(variant)
+            );
+        }
+    }
+    out
+}
@@ -0,0 +1,182 @@
+//! Prints the ordinals of every enum deriving `ordinalizer::Ordinal`
+//! in a source tree, so that changes to them show up in review.
+//!
+//! ```text
+//! ordinalize-report [--format text|json|markdown] [PATH...]
+//! ordinalize-report diff OLD NEW
+//! ```
+//!
+//! The `diff` command compares two trees, such as two git worktrees,
+//! and exits with a non-zero status if any ordinals changed in a way
+//! which breaks persisted values: a variant was reordered, removed or
+//! renamed. Appending variants is safe.
+//!
+//! Variants are numbered by the same analysis as the derive.
+//! Variants behind `#[cfg]` are assumed to be enabled, and
+//! flattened enums are looked up by name among the scanned enums.
+
+mod diff;
+mod format;
+mod scan;
+
+use format::Format;
+use scan::EnumReport;
+use std::path::{Path, PathBuf};
+use std::process;
+
+const USAGE: &str = "\
+usage: ordinalize-report [--format text|json|markdown] [PATH...]
+       ordinalize-report diff OLD NEW
+
+Prints the ordinals of every enum deriving `Ordinal` under the given
+files and directories, which default to the current directory.
+
+`diff` prints the enums whose ordinals differ between the OLD and NEW
+trees, and fails if any of the changes are breaking.";
+
+enum Command {
+    Report { format: Format, paths: Vec<PathBuf> },
+    Diff { old: PathBuf, new: PathBuf },
+}
+
+fn parse_args() -> Result<Command, String> {
+    let mut args = std::env::args().skip(1).peekable();
+    let diff = args.peek().map(String::as_str) == Some("diff");
+    if diff {
+        args.next();
+    }
+
+    let mut format = None;
+    let mut paths = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                process::exit(0);
+            }
+            "-f" | "--format" if !diff => {
+                let value = args.next().ok_or("`--format` needs a value")?;
+                format = Some(
+                    Format::parse(&value).ok_or_else(|| format!("unknown format `{}`", value))?,
+                );
+            }
+            _ if arg.starts_with('-') => return Err(format!("unknown option `{}`", arg)),
+            _ => paths.push(PathBuf::from(arg)),
+        }
+    }
+
+    if diff {
+        let mut paths = paths.into_iter();
+        match (paths.next(), paths.next(), paths.next()) {
+            (Some(old), Some(new), None) => Ok(Command::Diff { old, new }),
+            _ => Err("`diff` needs an OLD and a NEW path".to_owned()),
+        }
+    } else {
+        if paths.is_empty() {
+            paths.push(PathBuf::from("."));
+        }
+        Ok(Command::Report {
+            format: format.unwrap_or(Format::Text),
+            paths,
+        })
+    }
+}
+
+/// Returns the path of a file found by scanning `root`.
+fn full_path(root: &Path, file: &Path) -> PathBuf {
+    if root.is_dir() {
+        root.join(file)
+    } else {
+        file.to_path_buf()
+    }
+}
+
+/// Scans `path`, printing any problems to stderr. Returns the
+/// enums found and whether the derive would fail on any of them.
+fn scan(path: &Path) -> (Vec<EnumReport>, bool) {
+    let scan = scan::scan(path).unwrap_or_else(|err| {
+        eprintln!("error: failed to scan `{}`: {}", path.display(), err);
+        process::exit(2);
+    });
+
+    for error in &scan.errors {
+        eprintln!("warning: skipped {}", error);
+    }
+    let mut failed = false;
+    for report in &scan.enums {
+        if let Err(errors) = &report.variants {
+            failed = true;
+            for error in errors {
+                eprintln!(
+                    "error: {}:{}",
+                    full_path(path, &report.file).display(),
+                    error
+                );
+            }
+        }
+    }
+
+    (scan.enums, failed)
+}
+
+fn report(format: Format, paths: &[PathBuf]) -> bool {
+    let mut enums = Vec::new();
+    let mut failed = false;
+    for path in paths {
+        let (mut found, path_failed) = scan(path);
+        if paths.len() > 1 {
+            for report in &mut found {
+                report.file = full_path(path, &report.file);
+            }
+        }
+        enums.append(&mut found);
+        failed |= path_failed;
+    }
+
+    print!("{}", format.render(&enums));
+    !failed
+}
+
+fn diff(old: &Path, new: &Path) -> bool {
+    let (old_enums, old_failed) = scan(old);
+    let (new_enums, new_failed) = scan(new);
+    let diff = diff::diff(&old_enums, &new_enums);
+
+    print!("{}", diff::render(&diff));
+    for report in &diff.unknown {
+        eprintln!(
+            "error: {}:{}: the ordinals of `{}` could not be compared",
+            full_path(new, &report.file).display(),
+            report.line,
+            report.name
+        );
+    }
+
+    let breaking = diff.enums.iter().filter(|d| d.is_breaking()).count();
+    if diff.enums.is_empty() {
+        eprintln!("no ordinals changed");
+    } else {
+        eprintln!(
+            "{} enums changed, {} with breaking changes",
+            diff.enums.len(),
+            breaking
+        );
+    }
+
+    breaking == 0 && diff.unknown.is_empty() && !old_failed && !new_failed
+}
+
+fn main() {
+    let command = parse_args().unwrap_or_else(|err| {
+        eprintln!("error: {}\n\n{}", err, USAGE);
+        process::exit(2);
+    });
+
+    let success = match command {
+        Command::Report { format, paths } => report(format, &paths),
+        Command::Diff { old, new } => diff(&old, &new),
+    };
+    if !success {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,263 @@
+//! Finding the enums which derive `Ordinal` in a source tree.
+
+use ordinalizer_analysis::{detect_variants, variant_name, EnumAttrs};
+use quote::ToTokens;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use syn::visit::{self, Visit};
+
+/// An enum deriving `Ordinal`.
+pub struct EnumReport {
+    /// The file declaring the enum, relative to the scanned directory,
+    /// or as given if a single file was scanned.
+    pub file: PathBuf,
+    /// The line of the enum's name in `file`.
+    pub line: usize,
+    /// The path of the enum within its file, e.g. `net::Message`.
+    pub name: String,
+    /// The variants of the enum, or the errors the derive
+    /// would report for it.
+    pub variants: Result<Vec<VariantReport>, Vec<String>>,
+}
+
+/// A variant of an [`EnumReport`].
+pub struct VariantReport {
+    pub name: String,
+    /// The first ordinal of the variant. This is `None` for
+    /// skipped variants, and for variants whose ordinal depends
+    /// on a flattened enum which was not found.
+    pub ordinal: Option<usize>,
+    /// The number of ordinals taken by the variant. For flattened
+    /// variants, this is the variant count of the wrapped enum,
+    /// if it was found.
+    pub count: Option<usize>,
+    pub skipped: bool,
+    /// The type of the field of a flattened variant.
+    pub flatten: Option<String>,
+    /// Whether the variant is behind `#[cfg]`. The report
+    /// assumes that every such variant is enabled.
+    pub cfg: bool,
+}
+
+impl EnumReport {
+    /// Returns the number of ordinals used by the enum, if known.
+    pub fn variant_count(&self) -> Option<usize> {
+        let variants = self.variants.as_ref().ok()?;
+        let mut count = 0;
+        for variant in variants.iter().filter(|v| !v.skipped) {
+            count = count.max(variant.ordinal? + variant.count?);
+        }
+        Some(count)
+    }
+}
+
+/// The result of scanning a directory.
+#[derive(Default)]
+pub struct Scan {
+    pub enums: Vec<EnumReport>,
+    /// Files which could not be read or parsed.
+    pub errors: Vec<String>,
+}
+
+/// Scans every `.rs` file under `root` for enums deriving `Ordinal`,
+/// skipping hidden directories and `target` directories.
+pub fn scan(root: &Path) -> io::Result<Scan> {
+    let mut files = Vec::new();
+    collect_files(root, &mut files)?;
+    files.sort();
+
+    let mut scan = Scan::default();
+    for file in files {
+        let relative = match file.strip_prefix(root) {
+            Ok(relative) if root.is_dir() => relative.to_path_buf(),
+            _ => file.clone(),
+        };
+        let contents = match fs::read_to_string(&file) {
+            Ok(contents) => contents,
+            Err(err) => {
+                scan.errors.push(format!("{}: {}", relative.display(), err));
+                continue;
+            }
+        };
+        let syntax = match syn::parse_file(&contents) {
+            Ok(syntax) => syntax,
+            Err(err) => {
+                let line = err.span().start().line;
+                scan.errors
+                    .push(format!("{}:{}: {}", relative.display(), line, err));
+                continue;
+            }
+        };
+
+        let mut visitor = EnumVisitor {
+            file: relative,
+            modules: Vec::new(),
+            enums: &mut scan.enums,
+        };
+        visitor.visit_file(&syntax);
+    }
+
+    resolve_flattened(&mut scan.enums);
+    Ok(scan)
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    if dir.is_file() {
+        files.push(dir.to_path_buf());
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if path.is_dir() {
+            if !name.starts_with('.') && name != "target" {
+                collect_files(&path, files)?;
+            }
+        } else if name.ends_with(".rs") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+struct EnumVisitor<'a> {
+    file: PathBuf,
+    modules: Vec<String>,
+    enums: &'a mut Vec<EnumReport>,
+}
+
+impl<'ast> Visit<'ast> for EnumVisitor<'_> {
+    fn visit_item_mod(&mut self, item: &'ast syn::ItemMod) {
+        self.modules.push(item.ident.to_string());
+        visit::visit_item_mod(self, item);
+        self.modules.pop();
+    }
+
+    fn visit_item_enum(&mut self, item: &'ast syn::ItemEnum) {
+        if derives_ordinal(&item.attrs) {
+            let mut name = self.modules.join("::");
+            if !name.is_empty() {
+                name.push_str("::");
+            }
+            name.push_str(&item.ident.to_string());
+
+            self.enums.push(EnumReport {
+                file: self.file.clone(),
+                line: item.ident.span().start().line,
+                name,
+                variants: analyze(item.clone().into()),
+            });
+        }
+        visit::visit_item_enum(self, item);
+    }
+}
+
+/// Returns whether the attributes contain `#[derive(Ordinal)]`,
+/// under any path ending in `Ordinal`.
+fn derives_ordinal(attrs: &[syn::Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("derive"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::List(list)) => Some(list.nested),
+            _ => None,
+        })
+        .flatten()
+        .any(|nested| match nested {
+            syn::NestedMeta::Meta(syn::Meta::Path(path)) => path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "Ordinal"),
+            _ => false,
+        })
+}
+
+/// Numbers the variants of an enum exactly like the derive.
+fn analyze(input: syn::DeriveInput) -> Result<Vec<VariantReport>, Vec<String>> {
+    let into_messages = |err: syn::Error| {
+        err.into_iter()
+            .map(|err| format!("{}: {}", err.span().start().line, err))
+            .collect::<Vec<_>>()
+    };
+    let attrs = EnumAttrs::parse(&input.attrs).map_err(into_messages)?;
+    let variants = detect_variants(&input, &attrs).map_err(into_messages)?;
+
+    // The ordinals of enums with flattened variants depend on
+    // other enums, and are filled in by `resolve_flattened`.
+    let has_flatten = variants.iter().any(|v| v.flatten.is_some());
+    Ok(variants
+        .iter()
+        .map(|variant| VariantReport {
+            name: variant_name(variant.ident),
+            ordinal: variant.ordinal.filter(|_| !has_flatten),
+            count: match (variant.ordinal, variant.flatten) {
+                (None, _) => Some(0),
+                (Some(_), Some(_)) => None,
+                (Some(_), None) => Some(1),
+            },
+            skipped: variant.ordinal.is_none(),
+            flatten: variant
+                .flatten
+                .map(|field| field.ty.to_token_stream().to_string()),
+            cfg: !variant.cfgs.is_empty(),
+        })
+        .collect())
+}
+
+/// Numbers the variants of enums with flattened variants, by
+/// looking up the enums they wrap among the scanned enums.
+fn resolve_flattened(enums: &mut [EnumReport]) {
+    // Enums can wrap enums which wrap enums, so this repeats
+    // until no more ordinals become known.
+    loop {
+        let mut progress = false;
+        for i in 0..enums.len() {
+            let counts: Vec<Option<usize>> = match &enums[i].variants {
+                Ok(variants) => variants
+                    .iter()
+                    .map(|variant| match &variant.flatten {
+                        Some(ty) if variant.count.is_none() => flattened_count(enums, ty),
+                        _ => variant.count,
+                    })
+                    .collect(),
+                Err(_) => continue,
+            };
+
+            let variants = enums[i].variants.as_mut().unwrap();
+            let mut next = Some(0);
+            for (variant, count) in variants.iter_mut().zip(counts) {
+                if variant.skipped {
+                    continue;
+                }
+                if variant.count.is_none() && count.is_some() {
+                    variant.count = count;
+                    progress = true;
+                }
+                if variant.ordinal.is_none() && next.is_some() {
+                    variant.ordinal = next;
+                    progress = true;
+                }
+                next = next.zip(variant.count).map(|(next, count)| next + count);
+            }
+        }
+        if !progress {
+            break;
+        }
+    }
+}
+
+/// Returns the variant count of the enum named by the last
+/// segment of `ty`, if exactly one scanned enum has that name.
+fn flattened_count(enums: &[EnumReport], ty: &str) -> Option<usize> {
+    let name = ty.rsplit("::").next()?.trim();
+    let mut matches = enums
+        .iter()
+        .filter(|report| report.name.rsplit("::").next() == Some(name));
+    let report = matches.next()?;
+    if matches.next().is_some() {
+        return None;
+    }
+    report.variant_count()
+}
@@ -0,0 +1,79 @@
+use std::path::Path;
+use std::process::{Command, Output};
+
+fn diff(old: &str, new: &str) -> Output {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    Command::new(env!("CARGO_BIN_EXE_ordinalize-report"))
+        .current_dir(fixtures)
+        .args(["diff", old, new])
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> &str {
+    std::str::from_utf8(&output.stdout).unwrap()
+}
+
+#[test]
+fn breaking_changes() {
+    let output = diff("basic", "changed");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stdout(&output),
+        "\
+Event (src/events.rs:5), breaking
+  grown `Message` from 4 to 5 ordinals
+  reordered `Stop` from 5 to 6
+
+net::Message (src/lib.rs:7), safe
+  added `Quit` = 4
+
+Status (src/lib.rs:21), breaking
+  renamed `Done` to `Finished` = 2
+  reordered `Pending` from 0 to 1
+  reordered `Running` from 1 to 0
+
+Color (src/lib.rs:31), safe
+  enum added
+"
+    );
+}
+
+#[test]
+fn removed() {
+    let output = diff("changed", "basic");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).contains("removed `Quit` = 4"));
+    assert!(stdout(&output).contains("shrunk `Message` from 5 to 4 ordinals"));
+    assert!(stdout(&output).contains("Color (src/lib.rs:31), breaking\n  enum removed\n"));
+}
+
+#[test]
+fn append_only() {
+    // The enum moved to another file, which is not a change.
+    let output = diff("basic/src/lib.rs", "appended.rs");
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        "\
+net::Message (appended.rs:7), safe
+  added `Quit` = 4
+"
+    );
+}
+
+#[test]
+fn unchanged() {
+    let output = diff("basic", "basic");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "");
+}
+
+#[test]
+fn invalid_enum() {
+    let output = diff("basic", "invalid");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(std::str::from_utf8(&output.stderr)
+        .unwrap()
+        .contains("variant `B` has no `#[ordinal(index = ...)]`"));
+}
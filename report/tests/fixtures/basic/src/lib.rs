@@ -0,0 +1,33 @@
+use ordinalizer::Ordinal;
+
+pub mod net {
+    use super::*;
+
+    #[derive(Ordinal)]
+    pub enum Message {
+        Ping,
+        Chat(String),
+        #[ordinal(skip)]
+        Debug,
+        Move { x: f64, y: f64 },
+        #[cfg(feature = "admin")]
+        Kick,
+    }
+}
+
+#[derive(Debug, ordinalizer::Ordinal)]
+#[ordinal(explicit)]
+pub enum Status {
+    #[ordinal(index = 2)]
+    Done,
+    #[ordinal(index = 0)]
+    Pending,
+    #[ordinal(index = 1)]
+    Running,
+}
+
+// Not an ordinal enum.
+#[derive(Debug)]
+pub enum Other {
+    A,
+}
@@ -0,0 +1,10 @@
+use crate::net::Message;
+use ordinalizer::Ordinal;
+
+#[derive(Ordinal)]
+pub enum Event {
+    Start,
+    #[ordinal(flatten)]
+    Message(Message),
+    Stop,
+}
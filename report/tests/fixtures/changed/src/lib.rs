@@ -0,0 +1,34 @@
+use ordinalizer::Ordinal;
+
+pub mod net {
+    use super::*;
+
+    #[derive(Ordinal)]
+    pub enum Message {
+        Ping,
+        Chat(String),
+        #[ordinal(skip)]
+        Debug,
+        Move { x: f64, y: f64 },
+        #[cfg(feature = "admin")]
+        Kick,
+        Quit,
+    }
+}
+
+#[derive(Debug, ordinalizer::Ordinal)]
+#[ordinal(explicit)]
+pub enum Status {
+    #[ordinal(index = 2)]
+    Finished,
+    #[ordinal(index = 1)]
+    Pending,
+    #[ordinal(index = 0)]
+    Running,
+}
+
+#[derive(Ordinal)]
+pub enum Color {
+    Red,
+    Green,
+}
@@ -0,0 +1,9 @@
+use ordinalizer::Ordinal;
+
+#[derive(Ordinal)]
+#[ordinal(explicit)]
+pub enum Broken {
+    #[ordinal(index = 0)]
+    A,
+    B,
+}
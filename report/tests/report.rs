@@ -0,0 +1,109 @@
+use std::path::Path;
+use std::process::{Command, Output};
+
+fn report(args: &[&str]) -> Output {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    Command::new(env!("CARGO_BIN_EXE_ordinalize-report"))
+        .current_dir(fixtures)
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> &str {
+    std::str::from_utf8(&output.stdout).unwrap()
+}
+
+#[test]
+fn text() {
+    let output = report(&["basic"]);
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        "\
+Event (src/events.rs:5), 6 ordinals
+  Start          0
+  Message    1..=4  flatten(Message)
+  Stop           5
+
+net::Message (src/lib.rs:7), 4 ordinals
+  Ping         0
+  Chat         1
+  Debug  skipped
+  Move         2
+  Kick         3  cfg
+
+Status (src/lib.rs:20), 3 ordinals
+  Done           2
+  Pending        0
+  Running        1
+"
+    );
+}
+
+#[test]
+fn json() {
+    let output = report(&["--format", "json", "basic/src/lib.rs"]);
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_str(stdout(&output)).unwrap();
+    let enums = json["enums"].as_array().unwrap();
+    assert_eq!(enums.len(), 2);
+
+    let message = &enums[0];
+    assert_eq!(message["name"], "net::Message");
+    assert_eq!(message["variant_count"], 4);
+    let ordinals: Vec<_> = message["variants"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|variant| {
+            (
+                variant["name"].as_str().unwrap(),
+                variant["ordinal"].as_u64(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        ordinals,
+        [
+            ("Ping", Some(0)),
+            ("Chat", Some(1)),
+            ("Debug", None),
+            ("Move", Some(2)),
+            ("Kick", Some(3)),
+        ]
+    );
+}
+
+#[test]
+fn markdown() {
+    let output = report(&["-f", "markdown", "basic/src/events.rs"]);
+    assert!(output.status.success());
+    // `Message` is not among the scanned files, so the
+    // ordinals from the flattened variant on are unknown.
+    assert_eq!(
+        stdout(&output),
+        "\
+### `Event`
+
+`basic/src/events.rs:5`, ? ordinals
+
+| Variant | Ordinal | Notes |
+| --- | --- | --- |
+| `Start` | 0 |  |
+| `Message` | 1..? | flatten(Message) |
+| `Stop` | ? |  |
+"
+    );
+}
+
+#[test]
+fn invalid_enum() {
+    let output = report(&["invalid"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "");
+    assert!(std::str::from_utf8(&output.stderr)
+        .unwrap()
+        .contains("src/lib.rs:8: variant `B` has no `#[ordinal(index = ...)]`"));
+}
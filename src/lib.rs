@@ -6,6 +6,9 @@
 //! the variant of the enum and does not account
 //! for fields.
 //!
+//! The derive implements the [`Ordinal`] trait, so generic
+//! code can be written over any enum with ordinals.
+//!
 //! # Example
 //! ```
 //! use ordinalizer::Ordinal;
@@ -19,105 +22,275 @@
 //!
 //! assert_eq!(Animal::Dog.ordinal(), 0);
 //! assert_eq!((Animal::Cat { age: 10 }).ordinal(), 1);
+//!
+//! fn bucket<T: Ordinal>(value: &T) -> usize {
+//!     value.ordinal() % T::VARIANT_COUNT
+//! }
+//! assert_eq!(bucket(&Animal::Dog), 0);
 //! ```
 
-use proc_macro2::{Ident, TokenStream};
-use proc_macro_error::*;
-use quote::quote;
-use syn::{parse_macro_input, DeriveInput};
+#![no_std]
+
+pub mod map;
+pub mod set;
 
-struct Variant<'a> {
-    ident: &'a Ident,
-    unit_field_count: usize,
-    has_named_fields: bool,
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{Deref, DerefMut};
+
+pub use map::OrdinalMap;
+pub use ordinalizer_derive::Ordinal;
+pub use set::OrdinalSet;
+
+/// Items used by the code generated by `#[derive(Ordinal)]`.
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "serde")]
+    pub use serde;
 }
 
-/// Generates a `fn ordinal(&self) -> usize` for an enum.
+/// An enum whose variants each have an ordinal.
 ///
-/// The enum may have any number of variants. It is not
-/// required to be a C-like enum, i.e. its variants
-/// may have named or unnamed fields.
+/// Ordinals are distinct small integers starting at `0`. By default,
+/// `#[derive(Ordinal)]` numbers the variants in definition order, so
+/// the first variant has ordinal `0`; explicit indices, skipped
+/// variants, `stable_cfg` and flattened variants change the numbering
+/// as described on the derive.
 ///
-/// The returned ordinals will correspond to the variant's
-/// index in the enum definition. For example, the first
-/// variant of enum will have ordinal `0`.
-#[proc_macro_error]
-#[proc_macro_derive(Ordinal)]
-pub fn derive_ordinal(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
-
-    let variants = detect_variants(&input);
-
-    let match_arms = generate_match_arms(&variants, &input);
-
-    let enum_ident = &input.ident;
-
-    let tokens = quote! {
-        impl #enum_ident {
-            pub fn ordinal(&self) -> usize {
-                match self {
-                    #(#match_arms,)*
-                }
-            }
-        }
-    };
-    tokens.into()
+/// This trait is normally implemented with `#[derive(Ordinal)]`.
+pub trait Ordinal {
+    /// The number of ordinals used by the enum's variants, which
+    /// is one more than the largest ordinal. This may differ from
+    /// the number of variants, e.g. if ordinals are left unused
+    /// or if variants are flattened.
+    const VARIANT_COUNT: usize;
+
+    /// The largest ordinal of any variant in the enum.
+    ///
+    /// Using this constant on an enum without variants
+    /// is a compile error.
+    const MAX_ORDINAL: usize = Self::VARIANT_COUNT - 1;
+
+    /// The names of the enum's variants, indexed by ordinal.
+    ///
+    /// This holds `VARIANT_COUNT` names; ordinals which
+    /// no variant uses have an empty name.
+    const VARIANT_NAMES: &'static [&'static str];
+
+    /// Returns the ordinal of this value's variant.
+    ///
+    /// The returned value is always less than `VARIANT_COUNT`.
+    fn ordinal(&self) -> usize;
+
+    /// Returns the name of this value's variant.
+    fn variant_name(&self) -> &'static str;
+}
+
+/// An enum whose variants can be created from their ordinals.
+///
+/// `#[derive(Ordinal)]` implements this trait for enums
+/// without data-carrying variants, and for other enums
+/// when `#[ordinal(from_ordinal)]` is given.
+pub trait FromOrdinal: Ordinal + Sized {
+    /// Returns the variant with the given ordinal, or `None`
+    /// if no variant has that ordinal.
+    fn from_ordinal(ordinal: usize) -> Option<Self>;
+}
+
+/// The error returned when converting an ordinal
+/// which does not belong to any variant.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct InvalidOrdinal {
+    ordinal: usize,
+}
+
+impl InvalidOrdinal {
+    /// Creates an error for the given ordinal.
+    pub const fn new(ordinal: usize) -> Self {
+        Self { ordinal }
+    }
+
+    /// Returns the ordinal which failed to convert.
+    pub fn ordinal(&self) -> usize {
+        self.ordinal
+    }
+}
+
+impl fmt::Display for InvalidOrdinal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "no variant has ordinal {}", self.ordinal)
+    }
+}
+
+impl core::error::Error for InvalidOrdinal {}
+
+/// The kind of fields a variant has.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum FieldStyle {
+    /// A variant without fields, e.g. `Dog`.
+    Unit,
+    /// A variant with unnamed fields, e.g. `Cat(i32)`.
+    Tuple,
+    /// A variant with named fields, e.g. `Cat { age: i32 }`.
+    Named,
+}
+
+/// A description of a variant of an enum.
+///
+/// `#[derive(Ordinal)]` generates a `VARIANTS` constant holding
+/// one of these for each variant, in declaration order, and a
+/// `variant_info()` method returning the one of a value's variant.
+///
+/// # Example
+/// ```
+/// use ordinalizer::{FieldStyle, Ordinal};
+/// #[derive(Ordinal)]
+/// enum Animal {
+///     Dog,
+///     Cat { age: i32, name: String },
+/// }
+///
+/// let cat = Animal::Cat { age: 3, name: "Tom".into() }.variant_info();
+/// assert_eq!(cat.name(), "Cat");
+/// assert_eq!(cat.ordinal(), Some(1));
+/// assert_eq!(cat.style(), FieldStyle::Named);
+/// assert_eq!(cat.field_names(), ["age", "name"]);
+/// assert_eq!(Animal::VARIANTS[0].field_count(), 0);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct VariantInfo {
+    name: &'static str,
+    ordinal: Option<usize>,
+    style: FieldStyle,
+    field_count: usize,
+    field_names: &'static [&'static str],
 }
 
-fn detect_variants(input: &DeriveInput) -> Vec<Variant> {
-    let mut vec = Vec::new();
+impl VariantInfo {
+    /// Creates a description of a variant. `field_names` should
+    /// be empty unless `style` is [`FieldStyle::Named`].
+    pub const fn new(
+        name: &'static str,
+        ordinal: Option<usize>,
+        style: FieldStyle,
+        field_count: usize,
+        field_names: &'static [&'static str],
+    ) -> Self {
+        Self {
+            name,
+            ordinal,
+            style,
+            field_count,
+            field_names,
+        }
+    }
+
+    /// Returns the name of the variant.
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
 
-    let data = match &input.data {
-        syn::Data::Enum(data) => data,
-        _ => abort_call_site!("cannot derive `Ordinal` on an item which is not an enum"),
-    };
+    /// Returns the ordinal of the variant, or `None` if it is
+    /// skipped. For a flattened variant, this is the ordinal
+    /// of the first variant of the enum it wraps.
+    pub const fn ordinal(&self) -> Option<usize> {
+        self.ordinal
+    }
 
-    for variant in &data.variants {
-        vec.push(detect_variant(variant));
+    /// Returns the kind of fields the variant has.
+    pub const fn style(&self) -> FieldStyle {
+        self.style
     }
 
-    vec
+    /// Returns the number of fields of the variant.
+    pub const fn field_count(&self) -> usize {
+        self.field_count
+    }
+
+    /// Returns the names of the fields of the variant, in
+    /// declaration order. This is empty unless the variant
+    /// has named fields.
+    pub const fn field_names(&self) -> &'static [&'static str] {
+        self.field_names
+    }
 }
 
-fn detect_variant(variant: &syn::Variant) -> Variant {
-    let ident = &variant.ident;
+/// A wrapper which compares and hashes values by their
+/// variants alone, ignoring their fields.
+///
+/// This allows grouping or deduplicating values by variant,
+/// even if their fields do not implement `Eq` or `Hash`.
+///
+/// # Example
+/// ```
+/// use ordinalizer::{ByVariant, Ordinal};
+/// use std::collections::HashSet;
+/// #[derive(Ordinal)]
+/// enum Reading {
+///     Temperature(f64),
+///     Humidity(f64),
+/// }
+///
+/// let readings = vec![
+///     Reading::Temperature(21.5),
+///     Reading::Humidity(0.4),
+///     Reading::Temperature(22.0),
+/// ];
+/// let kinds: HashSet<_> = readings.into_iter().map(ByVariant).collect();
+/// assert_eq!(kinds.len(), 2);
+/// ```
+#[derive(Debug, Copy, Clone, Default)]
+pub struct ByVariant<T>(pub T);
 
-    let (unit_field_count, has_named_fields) = match &variant.fields {
-        syn::Fields::Named(_) => (0, true),
-        syn::Fields::Unit => (0, false),
-        syn::Fields::Unnamed(unnanmed) => (unnanmed.unnamed.len(), false),
-    };
+impl<T> ByVariant<T> {
+    /// Returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for ByVariant<T> {
+    fn from(value: T) -> Self {
+        ByVariant(value)
+    }
+}
+
+impl<T> Deref for ByVariant<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
 
-    Variant {
-        ident,
-        unit_field_count,
-        has_named_fields,
+impl<T> DerefMut for ByVariant<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
     }
 }
 
-fn generate_match_arms(variants: &[Variant], input: &DeriveInput) -> Vec<TokenStream> {
-    let mut vec = Vec::new();
-    let enum_ident = &input.ident;
+impl<T: Ordinal> PartialEq for ByVariant<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.ordinal() == other.0.ordinal()
+    }
+}
 
-    for (ordinal, variant) in variants.iter().enumerate() {
-        let variant_ident = variant.ident;
-        let pattern = match (variant.has_named_fields, variant.unit_field_count) {
-            (true, _) => quote! { #enum_ident::#variant_ident { .. } },
-            (false, x) if x != 0 => {
-                let underscores: Vec<_> = (0..x).map(|_| quote! { _ }).collect();
+impl<T: Ordinal> Eq for ByVariant<T> {}
 
-                quote! {
-                    #enum_ident::#variant_ident(#(#underscores),*)
-                }
-            }
-            (false, 0) => quote! { #enum_ident::#variant_ident },
-            _ => unreachable!(),
-        };
+impl<T: Ordinal> PartialOrd for ByVariant<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
 
-        vec.push(quote! {
-            #pattern => #ordinal
-        });
+impl<T: Ordinal> Ord for ByVariant<T> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.0.ordinal().cmp(&other.0.ordinal())
     }
+}
 
-    vec
+impl<T: Ordinal> Hash for ByVariant<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.ordinal().hash(state);
+    }
 }
@@ -0,0 +1,449 @@
+//! A map keyed by the variants of an enum.
+
+use crate::Ordinal;
+use core::fmt;
+use core::iter::{Enumerate, FromIterator, FusedIterator};
+use core::marker::PhantomData;
+use core::ops::{Index, IndexMut};
+use core::slice;
+
+/// An enum whose maps can store their values in an [`OrdinalMap`].
+///
+/// `#[derive(Ordinal)]` implements this trait along with [`Ordinal`],
+/// storing the values in an array of `VARIANT_COUNT` slots.
+pub trait OrdinalSlots: Ordinal {
+    /// The storage of an [`OrdinalMap`] of this enum with values of type `V`.
+    type Slots<V>: Slots<V>;
+}
+
+/// Storage for the values of an [`OrdinalMap`].
+///
+/// Slot `i` holds the value for the variant with ordinal `i`.
+pub trait Slots<V>: AsRef<[Option<V>]> + AsMut<[Option<V>]> {
+    /// An iterator over the slots, in ordinal order.
+    type IntoIter: DoubleEndedIterator<Item = Option<V>> + ExactSizeIterator;
+
+    /// Returns storage with every slot empty.
+    fn empty() -> Self;
+
+    /// Iterates over the slots, in ordinal order.
+    fn into_iter(self) -> Self::IntoIter;
+}
+
+impl<V, const N: usize> Slots<V> for [Option<V>; N] {
+    type IntoIter = core::array::IntoIter<Option<V>, N>;
+
+    fn empty() -> Self {
+        core::array::from_fn(|_| None)
+    }
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIterator::into_iter(self)
+    }
+}
+
+/// A map from the variants of `E` to values of type `V`.
+///
+/// The map holds one slot per ordinal of `E`, stored inline
+/// without allocating, so lookups are a single array access
+/// and the map is `Copy` if `V` is. Two keys of the same variant
+/// are the same key, regardless of their fields.
+///
+/// Iteration visits the entries in ordinal order and yields
+/// the ordinal of each entry along with its value.
+///
+/// # Example
+/// ```
+/// use ordinalizer::{Ordinal, OrdinalMap};
+/// #[derive(Ordinal)]
+/// enum Message {
+///     Ping,
+///     Chat(String),
+/// }
+///
+/// let mut counts = OrdinalMap::new();
+/// for message in &[Message::Ping, Message::Chat("hi".into()), Message::Ping] {
+///     *counts.entry(message).or_insert(0) += 1;
+/// }
+///
+/// assert_eq!(counts[&Message::Ping], 2);
+/// assert_eq!(counts[&Message::Chat(String::new())], 1);
+/// ```
+pub struct OrdinalMap<E: OrdinalSlots, V> {
+    slots: E::Slots<V>,
+    len: usize,
+    _marker: PhantomData<fn(&E)>,
+}
+
+impl<E: OrdinalSlots, V> OrdinalMap<E, V> {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self {
+            slots: Slots::empty(),
+            len: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns whether the map has an entry for the variant of `key`.
+    pub fn contains_key(&self, key: &E) -> bool {
+        self.slots.as_ref()[key.ordinal()].is_some()
+    }
+
+    /// Returns the value for the variant of `key`.
+    pub fn get(&self, key: &E) -> Option<&V> {
+        self.slots.as_ref()[key.ordinal()].as_ref()
+    }
+
+    /// Returns a mutable reference to the value for the variant of `key`.
+    pub fn get_mut(&mut self, key: &E) -> Option<&mut V> {
+        self.slots.as_mut()[key.ordinal()].as_mut()
+    }
+
+    /// Sets the value for the variant of `key`, returning
+    /// the previous value if there was one.
+    pub fn insert(&mut self, key: &E, value: V) -> Option<V> {
+        let previous = self.slots.as_mut()[key.ordinal()].replace(value);
+        if previous.is_none() {
+            self.len += 1;
+        }
+        previous
+    }
+
+    /// Removes the value for the variant of `key`, returning it
+    /// if there was one.
+    pub fn remove(&mut self, key: &E) -> Option<V> {
+        let previous = self.slots.as_mut()[key.ordinal()].take();
+        if previous.is_some() {
+            self.len -= 1;
+        }
+        previous
+    }
+
+    /// Returns the entry for the variant of `key`, for
+    /// in-place manipulation.
+    pub fn entry(&mut self, key: &E) -> Entry<'_, V> {
+        let ordinal = key.ordinal();
+        Entry {
+            slot: &mut self.slots.as_mut()[ordinal],
+            len: &mut self.len,
+            ordinal,
+        }
+    }
+
+    /// Returns the number of entries in the map.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Removes all entries from the map.
+    pub fn clear(&mut self) {
+        self.slots.as_mut().iter_mut().for_each(|slot| *slot = None);
+        self.len = 0;
+    }
+
+    /// Iterates over the ordinals and values of the
+    /// entries, in ordinal order.
+    pub fn iter(&self) -> Iter<'_, V> {
+        Iter {
+            inner: self.slots.as_ref().iter().enumerate(),
+            remaining: self.len,
+        }
+    }
+
+    /// Iterates over the ordinals and values of the entries,
+    /// in ordinal order, with mutable references to the values.
+    pub fn iter_mut(&mut self) -> IterMut<'_, V> {
+        IterMut {
+            inner: self.slots.as_mut().iter_mut().enumerate(),
+            remaining: self.len,
+        }
+    }
+
+    /// Iterates over the values of the entries, in ordinal order.
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &V> + ExactSizeIterator {
+        self.iter().map(|(_, value)| value)
+    }
+
+    /// Iterates over mutable references to the values of the
+    /// entries, in ordinal order.
+    pub fn values_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut V> + ExactSizeIterator {
+        self.iter_mut().map(|(_, value)| value)
+    }
+}
+
+impl<E: OrdinalSlots, V> Default for OrdinalMap<E, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: OrdinalSlots, V: Clone> Clone for OrdinalMap<E, V> {
+    fn clone(&self) -> Self {
+        let mut slots = E::Slots::<V>::empty();
+        slots.as_mut().clone_from_slice(self.slots.as_ref());
+        Self {
+            slots,
+            len: self.len,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<E: OrdinalSlots, V: Copy> Copy for OrdinalMap<E, V> where E::Slots<V>: Copy {}
+
+impl<E: OrdinalSlots, V: PartialEq> PartialEq for OrdinalMap<E, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.slots.as_ref() == other.slots.as_ref()
+    }
+}
+
+impl<E: OrdinalSlots, V: Eq> Eq for OrdinalMap<E, V> {}
+
+impl<E: OrdinalSlots, V: fmt::Debug> fmt::Debug for OrdinalMap<E, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Panics if the map has no entry for the variant of the key.
+impl<E: OrdinalSlots, V> Index<&E> for OrdinalMap<E, V> {
+    type Output = V;
+
+    fn index(&self, key: &E) -> &V {
+        self.get(key).expect("no entry for variant in `OrdinalMap`")
+    }
+}
+
+/// Panics if the map has no entry for the variant of the key.
+impl<E: OrdinalSlots, V> IndexMut<&E> for OrdinalMap<E, V> {
+    fn index_mut(&mut self, key: &E) -> &mut V {
+        self.get_mut(key)
+            .expect("no entry for variant in `OrdinalMap`")
+    }
+}
+
+impl<E: OrdinalSlots, V> FromIterator<(E, V)> for OrdinalMap<E, V> {
+    fn from_iter<I: IntoIterator<Item = (E, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<E: OrdinalSlots, V> Extend<(E, V)> for OrdinalMap<E, V> {
+    fn extend<I: IntoIterator<Item = (E, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(&key, value);
+        }
+    }
+}
+
+impl<'a, E: OrdinalSlots, V> IntoIterator for &'a OrdinalMap<E, V> {
+    type Item = (usize, &'a V);
+    type IntoIter = Iter<'a, V>;
+
+    fn into_iter(self) -> Iter<'a, V> {
+        self.iter()
+    }
+}
+
+impl<'a, E: OrdinalSlots, V> IntoIterator for &'a mut OrdinalMap<E, V> {
+    type Item = (usize, &'a mut V);
+    type IntoIter = IterMut<'a, V>;
+
+    fn into_iter(self) -> IterMut<'a, V> {
+        self.iter_mut()
+    }
+}
+
+impl<E: OrdinalSlots, V> IntoIterator for OrdinalMap<E, V> {
+    type Item = (usize, V);
+    type IntoIter = IntoIter<E, V>;
+
+    fn into_iter(self) -> IntoIter<E, V> {
+        IntoIter {
+            inner: self.slots.into_iter().enumerate(),
+            remaining: self.len,
+        }
+    }
+}
+
+/// A view into a single slot of an [`OrdinalMap`],
+/// which may or may not hold a value.
+pub struct Entry<'a, V> {
+    slot: &'a mut Option<V>,
+    len: &'a mut usize,
+    ordinal: usize,
+}
+
+impl<'a, V> Entry<'a, V> {
+    /// Returns the ordinal of the entry's variant.
+    pub fn ordinal(&self) -> usize {
+        self.ordinal
+    }
+
+    /// Inserts `default` if the entry is empty, and returns
+    /// a mutable reference to the value.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    /// Inserts the result of `default` if the entry is empty,
+    /// and returns a mutable reference to the value.
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        if self.slot.is_none() {
+            *self.len += 1;
+        }
+        self.slot.get_or_insert_with(default)
+    }
+
+    /// Inserts `V::default()` if the entry is empty, and returns
+    /// a mutable reference to the value.
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    /// Calls `f` with the value if the entry holds one.
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Self {
+        if let Some(value) = self.slot.as_mut() {
+            f(value);
+        }
+        self
+    }
+}
+
+macro_rules! slot_iterator {
+    ($(#[$attr:meta])* $name:ident[$($params:tt)*][$($args:tt)*], $inner:ty, $item:ty) => {
+        $(#[$attr])*
+        pub struct $name<$($params)*> {
+            inner: Enumerate<$inner>,
+            remaining: usize,
+        }
+
+        impl<$($params)*> Iterator for $name<$($args)*> {
+            type Item = (usize, $item);
+
+            fn next(&mut self) -> Option<Self::Item> {
+                let (ordinal, value) = self
+                    .inner
+                    .by_ref()
+                    .find_map(|(ordinal, slot)| Option::<$item>::from(slot).map(|value| (ordinal, value)))?;
+                self.remaining -= 1;
+                Some((ordinal, value))
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (self.remaining, Some(self.remaining))
+            }
+        }
+
+        impl<$($params)*> DoubleEndedIterator for $name<$($args)*> {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                let (ordinal, value) = self
+                    .inner
+                    .by_ref()
+                    .rev()
+                    .find_map(|(ordinal, slot)| Option::<$item>::from(slot).map(|value| (ordinal, value)))?;
+                self.remaining -= 1;
+                Some((ordinal, value))
+            }
+        }
+
+        impl<$($params)*> ExactSizeIterator for $name<$($args)*> {}
+
+        impl<$($params)*> FusedIterator for $name<$($args)*> {}
+    };
+}
+
+slot_iterator!(
+    /// An iterator over the entries of an [`OrdinalMap`].
+    Iter['a, V]['a, V],
+    slice::Iter<'a, Option<V>>,
+    &'a V
+);
+
+slot_iterator!(
+    /// A mutable iterator over the entries of an [`OrdinalMap`].
+    IterMut['a, V]['a, V],
+    slice::IterMut<'a, Option<V>>,
+    &'a mut V
+);
+
+slot_iterator!(
+    /// An owning iterator over the entries of an [`OrdinalMap`].
+    IntoIter[E: OrdinalSlots, V][E, V],
+    <E::Slots<V> as Slots<V>>::IntoIter,
+    V
+);
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use super::{OrdinalMap, OrdinalSlots};
+    use core::fmt;
+    use core::marker::PhantomData;
+    use serde::de::{Deserialize, Deserializer, Error, MapAccess, Visitor};
+    use serde::ser::{Serialize, SerializeMap, Serializer};
+
+    /// Serializes the map as a map from ordinals to values.
+    impl<E: OrdinalSlots, V: Serialize> Serialize for OrdinalMap<E, V> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut map = serializer.serialize_map(Some(self.len()))?;
+            for (ordinal, value) in self {
+                map.serialize_entry(&ordinal, value)?;
+            }
+            map.end()
+        }
+    }
+
+    /// Deserializes a map from ordinals to values, failing
+    /// on ordinals which are out of range for `E`.
+    impl<'de, E: OrdinalSlots, V: Deserialize<'de>> Deserialize<'de> for OrdinalMap<E, V> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_map(OrdinalMapVisitor(PhantomData))
+        }
+    }
+
+    struct OrdinalMapVisitor<E: OrdinalSlots, V>(PhantomData<fn() -> OrdinalMap<E, V>>);
+
+    impl<'de, E: OrdinalSlots, V: Deserialize<'de>> Visitor<'de> for OrdinalMapVisitor<E, V> {
+        type Value = OrdinalMap<E, V>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(
+                f,
+                "a map from ordinals below {} to values",
+                E::VARIANT_COUNT
+            )
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+            let mut map = OrdinalMap::<E, V>::new();
+            while let Some((ordinal, value)) = access.next_entry::<usize, V>()? {
+                let slot = map.slots.as_mut().get_mut(ordinal).ok_or_else(|| {
+                    A::Error::custom(format_args!(
+                        "ordinal {} is out of range for an enum with {} ordinals",
+                        ordinal,
+                        E::VARIANT_COUNT
+                    ))
+                })?;
+                if slot.replace(value).is_some() {
+                    return Err(A::Error::custom(format_args!(
+                        "duplicate ordinal {}",
+                        ordinal
+                    )));
+                }
+                map.len += 1;
+            }
+            Ok(map)
+        }
+    }
+}
@@ -0,0 +1,488 @@
+//! A bitset of the variants of an enum.
+
+use crate::Ordinal;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::{FromIterator, FusedIterator};
+use core::marker::PhantomData;
+use core::ops::{
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Sub, SubAssign,
+};
+
+/// An enum whose sets of variants can be stored in an [`OrdinalSet`].
+///
+/// `#[derive(Ordinal)]` implements this trait along with [`Ordinal`],
+/// choosing the smallest of `u8`, `u16`, `u32`, `u64` and `u128`
+/// which has a bit for every ordinal, or an array of `u64` words
+/// for enums with more than 128 ordinals.
+pub trait OrdinalBits: Ordinal {
+    /// The storage of an [`OrdinalSet`] of this enum.
+    type Bits: Bits;
+}
+
+/// Storage for the bits of an [`OrdinalSet`].
+///
+/// Bit `i` is set if the set contains the variant with ordinal `i`.
+pub trait Bits: Copy + Eq + Hash + fmt::Debug {
+    /// Storage with no bits set.
+    const EMPTY: Self;
+
+    /// Returns whether bit `index` is set.
+    fn contains(&self, index: usize) -> bool;
+
+    /// Sets bit `index`.
+    fn insert(&mut self, index: usize);
+
+    /// Clears bit `index`.
+    fn remove(&mut self, index: usize);
+
+    /// Returns the number of bits set.
+    fn count(&self) -> usize;
+
+    /// Returns the index of the lowest bit set.
+    fn first(&self) -> Option<usize>;
+
+    /// Returns the index of the highest bit set.
+    fn last(&self) -> Option<usize>;
+
+    /// Returns the bits set in either `self` or `other`.
+    fn union(self, other: Self) -> Self;
+
+    /// Returns the bits set in both `self` and `other`.
+    fn intersection(self, other: Self) -> Self;
+
+    /// Returns the bits set in exactly one of `self` and `other`.
+    fn symmetric_difference(self, other: Self) -> Self;
+
+    /// Returns the bits below `len` which are not set in `self`.
+    fn complement(self, len: usize) -> Self;
+}
+
+macro_rules! primitive_bits {
+    ($($ty:ty),*) => {$(
+        impl Bits for $ty {
+            const EMPTY: Self = 0;
+
+            fn contains(&self, index: usize) -> bool {
+                *self & (1 << index) != 0
+            }
+
+            fn insert(&mut self, index: usize) {
+                *self |= 1 << index;
+            }
+
+            fn remove(&mut self, index: usize) {
+                *self &= !(1 << index);
+            }
+
+            fn count(&self) -> usize {
+                self.count_ones() as usize
+            }
+
+            fn first(&self) -> Option<usize> {
+                if *self == 0 {
+                    None
+                } else {
+                    Some(self.trailing_zeros() as usize)
+                }
+            }
+
+            fn last(&self) -> Option<usize> {
+                if *self == 0 {
+                    None
+                } else {
+                    Some((<$ty>::BITS - 1 - self.leading_zeros()) as usize)
+                }
+            }
+
+            fn union(self, other: Self) -> Self {
+                self | other
+            }
+
+            fn intersection(self, other: Self) -> Self {
+                self & other
+            }
+
+            fn symmetric_difference(self, other: Self) -> Self {
+                self ^ other
+            }
+
+            fn complement(self, len: usize) -> Self {
+                let mask = if len >= <$ty>::BITS as usize {
+                    !0
+                } else {
+                    (1 << len) - 1
+                };
+                !self & mask
+            }
+        }
+    )*};
+}
+
+primitive_bits!(u8, u16, u32, u64, u128);
+
+impl<const N: usize> Bits for [u64; N] {
+    const EMPTY: Self = [0; N];
+
+    fn contains(&self, index: usize) -> bool {
+        self[index / 64].contains(index % 64)
+    }
+
+    fn insert(&mut self, index: usize) {
+        self[index / 64].insert(index % 64);
+    }
+
+    fn remove(&mut self, index: usize) {
+        self[index / 64].remove(index % 64);
+    }
+
+    fn count(&self) -> usize {
+        self.iter().map(Bits::count).sum()
+    }
+
+    fn first(&self) -> Option<usize> {
+        self.iter()
+            .enumerate()
+            .find_map(|(i, word)| Some(i * 64 + word.first()?))
+    }
+
+    fn last(&self) -> Option<usize> {
+        self.iter()
+            .enumerate()
+            .rev()
+            .find_map(|(i, word)| Some(i * 64 + word.last()?))
+    }
+
+    fn union(mut self, other: Self) -> Self {
+        self.iter_mut().zip(&other).for_each(|(a, b)| *a |= b);
+        self
+    }
+
+    fn intersection(mut self, other: Self) -> Self {
+        self.iter_mut().zip(&other).for_each(|(a, b)| *a &= b);
+        self
+    }
+
+    fn symmetric_difference(mut self, other: Self) -> Self {
+        self.iter_mut().zip(&other).for_each(|(a, b)| *a ^= b);
+        self
+    }
+
+    fn complement(mut self, len: usize) -> Self {
+        for (i, word) in self.iter_mut().enumerate() {
+            *word = word.complement(len.saturating_sub(i * 64));
+        }
+        self
+    }
+}
+
+/// A set of the variants of `E`, stored as one bit per ordinal.
+///
+/// Like [`OrdinalMap`](crate::OrdinalMap) keys, two values of the
+/// same variant are the same element, regardless of their fields.
+/// Iteration yields the ordinals of the variants in the set, in
+/// ascending order.
+///
+/// Sets of fixed variants can be built in const contexts with the
+/// `ordinal_set` function generated by `#[derive(Ordinal)]`.
+///
+/// # Example
+/// ```
+/// use ordinalizer::{Ordinal, OrdinalSet};
+/// #[derive(Ordinal)]
+/// enum Message {
+///     Ping,
+///     Chat(String),
+///     Quit,
+/// }
+///
+/// const CONTROL: OrdinalSet<Message> = Message::ordinal_set(&[Message::Ping, Message::Quit]);
+///
+/// assert!(CONTROL.contains(&Message::Quit));
+/// assert!(!CONTROL.contains(&Message::Chat("hi".into())));
+/// assert!((!CONTROL).contains(&Message::Chat(String::new())));
+/// assert_eq!(CONTROL.iter().collect::<Vec<_>>(), [0, 2]);
+/// ```
+pub struct OrdinalSet<E: OrdinalBits> {
+    bits: E::Bits,
+    _marker: PhantomData<fn(&E)>,
+}
+
+impl<E: OrdinalBits> OrdinalSet<E> {
+    /// Creates an empty set.
+    pub const fn new() -> Self {
+        Self::from_bits(E::Bits::EMPTY)
+    }
+
+    /// Creates a set from its bits, where bit `i` stands
+    /// for the variant with ordinal `i`.
+    ///
+    /// Bits at or above `VARIANT_COUNT` are ignored, except
+    /// when comparing and hashing sets.
+    pub const fn from_bits(bits: E::Bits) -> Self {
+        Self {
+            bits,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the bits of the set.
+    pub const fn bits(&self) -> E::Bits {
+        self.bits
+    }
+
+    /// Creates a set of every variant.
+    pub fn all() -> Self {
+        Self::new().complement()
+    }
+
+    /// Returns whether the set contains the variant of `value`.
+    pub fn contains(&self, value: &E) -> bool {
+        self.contains_ordinal(value.ordinal())
+    }
+
+    /// Returns whether the set contains the variant with the
+    /// given ordinal.
+    pub fn contains_ordinal(&self, ordinal: usize) -> bool {
+        ordinal < E::VARIANT_COUNT && self.bits.contains(ordinal)
+    }
+
+    /// Adds the variant of `value` to the set, returning
+    /// whether it was newly added.
+    pub fn insert(&mut self, value: &E) -> bool {
+        let ordinal = value.ordinal();
+        let added = !self.bits.contains(ordinal);
+        self.bits.insert(ordinal);
+        added
+    }
+
+    /// Removes the variant of `value` from the set, returning
+    /// whether it was present.
+    pub fn remove(&mut self, value: &E) -> bool {
+        let ordinal = value.ordinal();
+        let present = self.bits.contains(ordinal);
+        self.bits.remove(ordinal);
+        present
+    }
+
+    /// Removes every variant from the set.
+    pub fn clear(&mut self) {
+        self.bits = E::Bits::EMPTY;
+    }
+
+    /// Returns the number of variants in the set.
+    pub fn len(&self) -> usize {
+        self.in_range().count()
+    }
+
+    /// Returns whether the set has no variants.
+    pub fn is_empty(&self) -> bool {
+        self.in_range() == E::Bits::EMPTY
+    }
+
+    /// Returns the variants in either `self` or `other`.
+    pub fn union(self, other: Self) -> Self {
+        Self::from_bits(self.bits.union(other.bits))
+    }
+
+    /// Returns the variants in both `self` and `other`.
+    pub fn intersection(self, other: Self) -> Self {
+        Self::from_bits(self.bits.intersection(other.bits))
+    }
+
+    /// Returns the variants in `self` but not in `other`.
+    pub fn difference(self, other: Self) -> Self {
+        self.intersection(other.complement())
+    }
+
+    /// Returns the variants in exactly one of `self` and `other`.
+    pub fn symmetric_difference(self, other: Self) -> Self {
+        Self::from_bits(self.bits.symmetric_difference(other.bits))
+    }
+
+    /// Returns the variants not in `self`.
+    pub fn complement(self) -> Self {
+        Self::from_bits(self.bits.complement(E::VARIANT_COUNT))
+    }
+
+    /// Returns whether every variant in `self` is also in `other`.
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.intersection(*other) == *self
+    }
+
+    /// Returns whether every variant in `other` is also in `self`.
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Returns whether `self` and `other` have no variants in common.
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.intersection(*other).is_empty()
+    }
+
+    /// Iterates over the ordinals of the variants in the set,
+    /// in ascending order.
+    pub fn iter(&self) -> Iter<E> {
+        Iter {
+            bits: self.in_range(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the bits of the set which belong to an ordinal.
+    fn in_range(&self) -> E::Bits {
+        let mask = E::Bits::EMPTY.complement(E::VARIANT_COUNT);
+        self.bits.intersection(mask)
+    }
+}
+
+impl<E: OrdinalBits> Default for OrdinalSet<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: OrdinalBits> Clone for OrdinalSet<E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E: OrdinalBits> Copy for OrdinalSet<E> {}
+
+impl<E: OrdinalBits> PartialEq for OrdinalSet<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bits == other.bits
+    }
+}
+
+impl<E: OrdinalBits> Eq for OrdinalSet<E> {}
+
+impl<E: OrdinalBits> Hash for OrdinalSet<E> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bits.hash(state);
+    }
+}
+
+impl<E: OrdinalBits> fmt::Debug for OrdinalSet<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<E: OrdinalBits> FromIterator<E> for OrdinalSet<E> {
+    fn from_iter<I: IntoIterator<Item = E>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<E: OrdinalBits> Extend<E> for OrdinalSet<E> {
+    fn extend<I: IntoIterator<Item = E>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(&value);
+        }
+    }
+}
+
+impl<'a, E: OrdinalBits> Extend<&'a E> for OrdinalSet<E> {
+    fn extend<I: IntoIterator<Item = &'a E>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<E: OrdinalBits> IntoIterator for OrdinalSet<E> {
+    type Item = usize;
+    type IntoIter = Iter<E>;
+
+    fn into_iter(self) -> Iter<E> {
+        self.iter()
+    }
+}
+
+impl<E: OrdinalBits> IntoIterator for &OrdinalSet<E> {
+    type Item = usize;
+    type IntoIter = Iter<E>;
+
+    fn into_iter(self) -> Iter<E> {
+        self.iter()
+    }
+}
+
+macro_rules! set_operator {
+    ($($op:ident::$method:ident, $assign:ident::$assign_method:ident => $set_method:ident;)*) => {$(
+        impl<E: OrdinalBits> $op for OrdinalSet<E> {
+            type Output = Self;
+
+            fn $method(self, other: Self) -> Self {
+                self.$set_method(other)
+            }
+        }
+
+        impl<E: OrdinalBits> $assign for OrdinalSet<E> {
+            fn $assign_method(&mut self, other: Self) {
+                *self = self.$set_method(other);
+            }
+        }
+    )*};
+}
+
+set_operator! {
+    BitOr::bitor, BitOrAssign::bitor_assign => union;
+    BitAnd::bitand, BitAndAssign::bitand_assign => intersection;
+    BitXor::bitxor, BitXorAssign::bitxor_assign => symmetric_difference;
+    Sub::sub, SubAssign::sub_assign => difference;
+}
+
+impl<E: OrdinalBits> Not for OrdinalSet<E> {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        self.complement()
+    }
+}
+
+/// An iterator over the ordinals in an [`OrdinalSet`].
+pub struct Iter<E: OrdinalBits> {
+    bits: E::Bits,
+    _marker: PhantomData<fn(&E)>,
+}
+
+impl<E: OrdinalBits> Clone for Iter<E> {
+    fn clone(&self) -> Self {
+        Self {
+            bits: self.bits,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<E: OrdinalBits> Iterator for Iter<E> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let ordinal = self.bits.first()?;
+        self.bits.remove(ordinal);
+        Some(ordinal)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bits.count();
+        (len, Some(len))
+    }
+}
+
+impl<E: OrdinalBits> DoubleEndedIterator for Iter<E> {
+    fn next_back(&mut self) -> Option<usize> {
+        let ordinal = self.bits.last()?;
+        self.bits.remove(ordinal);
+        Some(ordinal)
+    }
+}
+
+impl<E: OrdinalBits> ExactSizeIterator for Iter<E> {}
+
+impl<E: OrdinalBits> FusedIterator for Iter<E> {}
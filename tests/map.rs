@@ -0,0 +1,159 @@
+#![allow(dead_code)]
+
+use ordinalizer::{Ordinal, OrdinalMap};
+
+#[derive(Debug, Ordinal)]
+enum Message {
+    Ping,
+    Chat(String),
+    Move { x: f64, y: f64 },
+    Quit,
+}
+
+#[test]
+fn insert_and_get() {
+    let mut map = OrdinalMap::new();
+    assert!(map.is_empty());
+
+    assert_eq!(map.insert(&Message::Chat("a".into()), 1), None);
+    assert_eq!(map.insert(&Message::Quit, 2), None);
+    assert_eq!(map.insert(&Message::Chat("b".into()), 3), Some(1));
+
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get(&Message::Chat(String::new())), Some(&3));
+    assert_eq!(map.get(&Message::Ping), None);
+    assert!(map.contains_key(&Message::Quit));
+
+    map[&Message::Quit] += 10;
+    assert_eq!(map[&Message::Quit], 12);
+
+    assert_eq!(map.remove(&Message::Quit), Some(12));
+    assert_eq!(map.remove(&Message::Quit), None);
+    assert_eq!(map.len(), 1);
+
+    map.clear();
+    assert!(map.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "no entry for variant")]
+fn index_missing() {
+    let map: OrdinalMap<Message, u32> = OrdinalMap::new();
+    let _ = map[&Message::Ping];
+}
+
+#[test]
+fn entry() {
+    let mut map: OrdinalMap<_, u32> = OrdinalMap::new();
+    let messages = [
+        Message::Ping,
+        Message::Move { x: 1.0, y: 2.0 },
+        Message::Ping,
+    ];
+    for message in &messages {
+        *map.entry(message).or_default() += 1;
+    }
+    map.entry(&Message::Quit).and_modify(|count| *count += 1);
+    map.entry(&Message::Ping).and_modify(|count| *count *= 10);
+
+    assert_eq!(map.entry(&Message::Quit).ordinal(), 3);
+    assert_eq!(map.len(), 2);
+    assert_eq!(map[&Message::Ping], 20);
+    assert_eq!(map[&Message::Move { x: 0.0, y: 0.0 }], 1);
+}
+
+#[test]
+fn iteration_in_ordinal_order() {
+    let mut map: OrdinalMap<_, _> = vec![
+        (Message::Quit, "quit"),
+        (Message::Ping, "ping"),
+        (Message::Chat("hi".into()), "chat"),
+    ]
+    .into_iter()
+    .collect();
+
+    let entries: Vec<_> = map.iter().collect();
+    assert_eq!(entries, [(0, &"ping"), (1, &"chat"), (3, &"quit")]);
+    assert_eq!(map.iter().len(), 3);
+    assert_eq!(map.iter().next_back(), Some((3, &"quit")));
+
+    for (_, value) in &mut map {
+        *value = "seen";
+    }
+    assert!(map.values().all(|value| *value == "seen"));
+
+    let owned: Vec<_> = map.into_iter().map(|(ordinal, _)| ordinal).collect();
+    assert_eq!(owned, [0, 1, 3]);
+}
+
+#[test]
+fn debug_and_eq() {
+    let mut a = OrdinalMap::new();
+    a.insert(&Message::Ping, String::from("one"));
+    let mut b = a.clone();
+    assert_eq!(a, b);
+
+    b.insert(&Message::Quit, String::from("two"));
+    assert_ne!(a, b);
+    assert_eq!(format!("{:?}", b), r#"{0: "one", 3: "two"}"#);
+}
+
+#[test]
+fn inline_and_copy() {
+    use std::mem::size_of;
+
+    assert_eq!(
+        size_of::<OrdinalMap<Message, u32>>(),
+        size_of::<[Option<u32>; 4]>() + size_of::<usize>()
+    );
+
+    let mut a = OrdinalMap::new();
+    a.insert(&Message::Ping, 1u32);
+    let mut b = a;
+    b.insert(&Message::Quit, 2);
+    assert_eq!(a.len(), 1);
+    assert_eq!(b.len(), 2);
+}
+
+#[test]
+fn generic_and_flattened_keys() {
+    #[derive(Ordinal)]
+    enum Wrapper<V> {
+        Value(V),
+        Empty,
+    }
+
+    #[derive(Ordinal)]
+    enum Outer {
+        #[ordinal(flatten)]
+        Message(Message),
+        Other,
+    }
+
+    let mut map = OrdinalMap::new();
+    map.insert(&Wrapper::Empty, "empty");
+    assert_eq!(map.get(&Wrapper::<u8>::Value(1)), None);
+    assert_eq!(map[&Wrapper::Empty], "empty");
+
+    let mut map = OrdinalMap::new();
+    map.insert(&Outer::Message(Message::Quit), 3);
+    map.insert(&Outer::Other, 4);
+    assert_eq!(map.into_iter().collect::<Vec<_>>(), [(3, 3), (4, 4)]);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde() {
+    let mut map = OrdinalMap::new();
+    map.insert(&Message::Chat(String::new()), 5);
+    map.insert(&Message::Quit, 7);
+
+    let json = serde_json::to_string(&map).unwrap();
+    assert_eq!(json, r#"{"1":5,"3":7}"#);
+
+    let back: OrdinalMap<Message, i32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, map);
+
+    let error = serde_json::from_str::<OrdinalMap<Message, i32>>(r#"{"4":1}"#).unwrap_err();
+    assert!(error.to_string().contains("ordinal 4 is out of range"));
+}
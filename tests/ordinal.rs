@@ -1,4 +1,7 @@
-use ordinalizer::Ordinal;
+#![allow(dead_code)]
+
+use ordinalizer::{FieldStyle, FromOrdinal, InvalidOrdinal, Ordinal, VariantInfo};
+use std::convert::TryFrom;
 
 #[test]
 fn basic() {
@@ -47,3 +50,784 @@ fn fields() {
     assert_eq!(Test::C(10, 0).ordinal(), 2);
     assert_eq!(Test::D.ordinal(), 3);
 }
+
+#[test]
+fn generic_over_trait() {
+    #[derive(Ordinal)]
+    enum Test {
+        A,
+        B(u8),
+        C,
+    }
+
+    fn ordinals<T: Ordinal>(values: &[T]) -> Vec<usize> {
+        values.iter().map(T::ordinal).collect()
+    }
+
+    assert_eq!(Test::VARIANT_COUNT, 3);
+    assert_eq!(ordinals(&[Test::C, Test::B(5), Test::A]), vec![2, 1, 0]);
+}
+
+#[test]
+fn lifetimes() {
+    #[derive(Ordinal)]
+    enum Test<'a, 'b: 'a> {
+        A(&'a str),
+        B { named: &'b [u8] },
+        C,
+    }
+
+    let text = String::from("test");
+    assert_eq!(Test::A(&text).ordinal(), 0);
+    assert_eq!(Test::B { named: &[1, 2] }.ordinal(), 1);
+    assert_eq!(Test::C.ordinal(), 2);
+}
+
+#[test]
+fn type_parameters() {
+    #[derive(Ordinal)]
+    enum Test<T: Clone, U = i32>
+    where
+        U: Default,
+    {
+        A(T),
+        B(T, U),
+        C,
+    }
+
+    assert_eq!(Test::<String>::A(String::new()).ordinal(), 0);
+    assert_eq!(Test::B(1u8, 2u64).ordinal(), 1);
+    assert_eq!(Test::<(), ()>::C.ordinal(), 2);
+    assert_eq!(<Test<String> as Ordinal>::VARIANT_COUNT, 3);
+}
+
+#[test]
+fn const_generics() {
+    #[derive(Ordinal)]
+    enum Test<'a, T, const N: usize> {
+        A([T; N]),
+        B(&'a [T; N]),
+    }
+
+    assert_eq!(Test::A([0u8; 4]).ordinal(), 0);
+    assert_eq!(Test::B(&[1u16, 2, 3]).ordinal(), 1);
+}
+
+#[test]
+fn variant_count() {
+    #[derive(Ordinal)]
+    enum Test {
+        A,
+        B(i32),
+        C { _named: bool },
+    }
+
+    #[derive(Ordinal)]
+    enum Empty {}
+
+    const COUNT: usize = Test::VARIANT_COUNT;
+    let table = [0u32; Test::VARIANT_COUNT];
+
+    assert_eq!(COUNT, 3);
+    assert_eq!(table.len(), 3);
+    assert_eq!(Test::MAX_ORDINAL, 2);
+    assert_eq!(<Test as Ordinal>::MAX_ORDINAL, 2);
+    assert_eq!(Test::C { _named: true }.ordinal(), Test::MAX_ORDINAL);
+    assert_eq!(Empty::VARIANT_COUNT, 0);
+}
+
+#[test]
+fn from_ordinal() {
+    #[derive(Debug, PartialEq, Ordinal)]
+    enum Test {
+        A,
+        B,
+        C,
+    }
+
+    assert_eq!(Test::from_ordinal(0), Some(Test::A));
+    assert_eq!(Test::from_ordinal(2), Some(Test::C));
+    assert_eq!(Test::from_ordinal(3), None);
+    assert_eq!(<Test as FromOrdinal>::from_ordinal(1), Some(Test::B));
+    assert_eq!(Test::try_from(1), Ok(Test::B));
+    assert_eq!(Test::try_from(7), Err(InvalidOrdinal::new(7)));
+}
+
+#[test]
+fn from_ordinal_with_fields() {
+    #[derive(Debug, PartialEq, Ordinal)]
+    #[ordinal(from_ordinal, on_skip = "panic")]
+    enum Test<T> {
+        A,
+        #[ordinal(skip)]
+        B(T, u8),
+        C,
+    }
+
+    assert_eq!(Test::<()>::from_ordinal(0), Some(Test::A));
+    assert_eq!(Test::<String>::try_from(1), Ok(Test::C));
+    assert_eq!(<Test<()> as FromOrdinal>::from_ordinal(2), None);
+}
+
+#[test]
+fn const_ordinal() {
+    #[derive(Debug, PartialEq, Ordinal)]
+    enum Test {
+        A,
+        B(u32),
+        C { _named: &'static str },
+    }
+
+    #[derive(Debug, PartialEq, Ordinal)]
+    enum Fieldless {
+        A,
+        B,
+    }
+
+    const B: usize = Test::B(10).ordinal();
+    static NAMES: [&str; Test::VARIANT_COUNT] = ["a", "b", "c"];
+    let table = [0u8; Test::C { _named: "c" }.ordinal()];
+    const FIELDLESS: Option<Fieldless> = Fieldless::from_ordinal(1);
+
+    assert_eq!(B, 1);
+    assert_eq!(NAMES[Test::A.ordinal()], "a");
+    assert_eq!(table.len(), 2);
+    assert_eq!(FIELDLESS, Some(Fieldless::B));
+}
+
+#[test]
+fn ordinal_consts() {
+    #[derive(Ordinal)]
+    enum Test {
+        Dog,
+        Cat { _age: i32 },
+        NetMsg(u8),
+        HTTPRequest,
+        Ipv4,
+    }
+
+    assert_eq!(Test::ORDINAL_DOG, 0);
+    assert_eq!(Test::ORDINAL_CAT, 1);
+    assert_eq!(Test::ORDINAL_NET_MSG, 2);
+    assert_eq!(Test::ORDINAL_HTTP_REQUEST, 3);
+    assert_eq!(Test::ORDINAL_IPV4, 4);
+
+    let describe = |test: &Test| match test.ordinal() {
+        Test::ORDINAL_CAT => "cat",
+        Test::ORDINAL_NET_MSG => "message",
+        _ => "other",
+    };
+    assert_eq!(describe(&Test::Cat { _age: 3 }), "cat");
+    assert_eq!(describe(&Test::NetMsg(0)), "message");
+    assert_eq!(describe(&Test::Dog), "other");
+}
+
+#[test]
+fn kind() {
+    #[derive(Ordinal)]
+    #[ordinal(kind = "AnimalKind")]
+    enum Animal<'a> {
+        /// A dog.
+        Dog,
+        Cat {
+            _name: &'a str,
+        },
+        Bird(f64),
+    }
+
+    let animals = [Animal::Bird(1.5), Animal::Dog, Animal::Cat { _name: "tom" }];
+    let mut kinds: Vec<AnimalKind> = animals.iter().map(Animal::kind).collect();
+    kinds.sort();
+
+    assert_eq!(kinds, [AnimalKind::Dog, AnimalKind::Cat, AnimalKind::Bird]);
+    assert_eq!(AnimalKind::Cat.ordinal(), Animal::ORDINAL_CAT);
+    assert_eq!(AnimalKind::VARIANT_COUNT, Animal::VARIANT_COUNT);
+    assert_eq!(AnimalKind::from_ordinal(2), Some(AnimalKind::Bird));
+
+    let set: std::collections::HashSet<_> = animals.iter().map(Animal::kind).collect();
+    assert!(set.contains(&AnimalKind::Dog));
+}
+
+#[test]
+fn variant_names() {
+    #[derive(Ordinal)]
+    enum Test {
+        Dog,
+        Cat { _age: i32 },
+        r#Type(u8),
+    }
+
+    fn label<T: Ordinal>(value: &T) -> String {
+        format!("{}/{}", value.variant_name(), T::VARIANT_NAMES.len())
+    }
+
+    const NAME: &str = Test::Dog.variant_name();
+
+    assert_eq!(Test::VARIANT_NAMES, ["Dog", "Cat", "Type"]);
+    assert_eq!(NAME, "Dog");
+    assert_eq!(Test::Cat { _age: 1 }.variant_name(), "Cat");
+    assert_eq!(Test::r#Type(0).variant_name(), "Type");
+    assert_eq!(label(&Test::Cat { _age: 1 }), "Cat/3");
+}
+
+#[test]
+fn repr() {
+    #[derive(Debug, PartialEq, Ordinal)]
+    #[ordinal(repr = "u8", kind = "TestKind")]
+    enum Test {
+        A(u32),
+        B,
+        C { _named: () },
+    }
+
+    #[derive(Debug, PartialEq, Ordinal)]
+    #[repr(u16)]
+    enum Fieldless {
+        A = 7,
+        B = 3,
+    }
+
+    let ordinal: u8 = Test::B.ordinal();
+    let max: u8 = Test::MAX_ORDINAL;
+    assert_eq!(ordinal, 1);
+    assert_eq!(max, 2);
+    assert_eq!(Test::ORDINAL_C, 2u8);
+    assert_eq!(<Test as Ordinal>::ordinal(&Test::C { _named: () }), 2usize);
+    assert_eq!(TestKind::A.ordinal(), Test::A(5).ordinal());
+
+    let ordinal: u16 = Fieldless::B.ordinal();
+    assert_eq!(ordinal, 1);
+    assert_eq!(Fieldless::from_ordinal(0u16), Some(Fieldless::A));
+    assert_eq!(Fieldless::try_from(1u16), Ok(Fieldless::B));
+    assert_eq!(Fieldless::try_from(1usize), Ok(Fieldless::B));
+    assert_eq!(
+        Fieldless::try_from(65537usize),
+        Err(InvalidOrdinal::new(65537))
+    );
+    assert_eq!(<Fieldless as FromOrdinal>::from_ordinal(65536), None);
+}
+
+#[test]
+fn explicit_indices() {
+    #[derive(Debug, PartialEq, Ordinal)]
+    #[ordinal(kind = "TestKind")]
+    enum Test {
+        #[ordinal(index = 2)]
+        A,
+        B(u8),
+        #[ordinal(index = 0)]
+        C,
+        D {
+            _named: bool,
+        },
+    }
+
+    assert_eq!(Test::A.ordinal(), 2);
+    assert_eq!(Test::B(0).ordinal(), 3);
+    assert_eq!(Test::C.ordinal(), 0);
+    assert_eq!(Test::D { _named: true }.ordinal(), 1);
+    assert_eq!(Test::VARIANT_NAMES, ["C", "D", "A", "B"]);
+    assert_eq!(TestKind::from_ordinal(3), Some(TestKind::B));
+    assert!(TestKind::C < TestKind::A);
+}
+
+#[test]
+fn explicit_mode() {
+    #[derive(Debug, PartialEq, Ordinal)]
+    #[ordinal(explicit)]
+    enum Test {
+        #[ordinal(index = 1)]
+        Apple,
+        #[ordinal(index = 2)]
+        Banana,
+        #[ordinal(index = 0)]
+        Cherry,
+    }
+
+    assert_eq!(Test::Apple.ordinal(), 1);
+    assert_eq!(Test::from_ordinal(0), Some(Test::Cherry));
+    assert_eq!(Test::from_ordinal(2), Some(Test::Banana));
+}
+
+#[test]
+fn cfg_variants() {
+    #[derive(Debug, PartialEq, Ordinal)]
+    #[ordinal(kind = "TestKind")]
+    enum Test {
+        A,
+        #[cfg(any())]
+        B(u8),
+        C,
+        #[cfg(not(any()))]
+        D,
+        #[cfg(any())]
+        E,
+    }
+
+    assert_eq!(Test::A.ordinal(), 0);
+    assert_eq!(Test::C.ordinal(), 1);
+    assert_eq!(Test::D.ordinal(), 2);
+    assert_eq!(Test::ORDINAL_D, 2);
+    assert_eq!(Test::VARIANT_COUNT, 3);
+    assert_eq!(Test::VARIANT_NAMES, ["A", "C", "D"]);
+    assert_eq!(Test::from_ordinal(2), Some(Test::D));
+    assert_eq!(Test::from_ordinal(3), None);
+    assert_eq!(TestKind::VARIANT_COUNT, 3);
+    assert_eq!(Test::D.kind().ordinal(), 2);
+}
+
+#[test]
+fn stable_cfg_variants() {
+    #[derive(Debug, PartialEq, Ordinal)]
+    #[ordinal(stable_cfg, kind = "TestKind")]
+    enum Test {
+        A,
+        #[cfg(any())]
+        B(u8),
+        #[ordinal(index = 2)]
+        C,
+        #[cfg(not(any()))]
+        D,
+        #[ordinal(index = 4)]
+        E,
+    }
+
+    assert_eq!(Test::A.ordinal(), 0);
+    assert_eq!(Test::C.ordinal(), 2);
+    assert_eq!(Test::D.ordinal(), 3);
+    assert_eq!(Test::E.ordinal(), 4);
+    assert_eq!(Test::VARIANT_COUNT, 5);
+    assert_eq!(Test::VARIANT_NAMES, ["A", "", "C", "D", "E"]);
+    assert_eq!(Test::VARIANT_NAMES[Test::C.ordinal()], "C");
+    assert_eq!(Test::VARIANT_NAMES[Test::E.ordinal()], "E");
+    assert_eq!(Test::from_ordinal(1), None);
+    assert_eq!(Test::from_ordinal(2), Some(Test::C));
+    assert_eq!(TestKind::from_ordinal(1), None);
+    assert_eq!(Test::C.kind().ordinal(), 2);
+}
+
+#[test]
+fn skip() {
+    #[derive(Debug, PartialEq, Ordinal)]
+    #[ordinal(kind = "TestKind")]
+    enum Test {
+        A,
+        #[ordinal(skip)]
+        Sentinel(u8),
+        B,
+        C,
+    }
+
+    assert_eq!(Test::A.ordinal(), Some(0));
+    assert_eq!(Test::Sentinel(1).ordinal(), None);
+    assert_eq!(Test::C.ordinal(), Some(2));
+    assert_eq!(Test::VARIANT_COUNT, 3);
+    assert_eq!(Test::VARIANT_NAMES, ["A", "B", "C"]);
+    assert_eq!(Test::Sentinel(1).variant_name(), "Sentinel");
+    assert_eq!(TestKind::Sentinel.ordinal(), None);
+    assert_eq!(TestKind::B.ordinal(), Some(1));
+    assert_eq!(Test::from_ordinal(1), Some(Test::B));
+    assert_eq!(Test::from_ordinal(3), None);
+    assert_eq!(Test::try_from(2), Ok(Test::C));
+}
+
+#[test]
+fn skip_with_fallback() {
+    #[derive(Debug, PartialEq, Ordinal)]
+    #[ordinal(on_skip = 0)]
+    enum Test {
+        Unknown,
+        A,
+        #[ordinal(skip)]
+        __Nonexhaustive,
+        B,
+    }
+
+    assert_eq!(Test::__Nonexhaustive.ordinal(), 0);
+    assert_eq!(Test::B.ordinal(), 2);
+    assert_eq!(<Test as Ordinal>::ordinal(&Test::__Nonexhaustive), 0);
+    assert_eq!(Test::from_ordinal(0), Some(Test::Unknown));
+    assert_eq!(Test::from_ordinal(3), None);
+}
+
+#[test]
+#[should_panic(expected = "`Test::Internal` has no ordinal")]
+fn skip_with_panic() {
+    #[derive(Ordinal)]
+    #[ordinal(on_skip = "panic")]
+    enum Test {
+        A,
+        #[ordinal(skip)]
+        Internal,
+    }
+
+    assert_eq!(Test::A.ordinal(), 0);
+    assert_eq!(Test::VARIANT_COUNT, 1);
+    Test::Internal.ordinal();
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn serde() {
+    #[derive(Debug, PartialEq, Ordinal)]
+    #[ordinal(serde, repr = "u8")]
+    enum Test {
+        A,
+        #[ordinal(index = 2)]
+        C,
+        #[ordinal(index = 1)]
+        B,
+    }
+
+    assert_eq!(serde_json::to_string(&Test::C).unwrap(), "2");
+    assert_eq!(serde_json::to_string(&[Test::A, Test::B]).unwrap(), "[0,1]");
+    assert_eq!(serde_json::from_str::<Test>("1").unwrap(), Test::B);
+
+    let error = serde_json::from_str::<Test>("3").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "invalid value: integer `3`, expected an ordinal of `Test` below 3"
+    );
+    assert!(serde_json::from_str::<Test>("256").is_err());
+}
+
+#[test]
+fn ord() {
+    #[derive(Debug, Ordinal)]
+    #[ordinal(ord)]
+    enum Priority {
+        #[ordinal(index = 2)]
+        Low(f64),
+        #[ordinal(index = 1)]
+        Normal { weight: f64 },
+        #[ordinal(index = 0)]
+        High,
+    }
+
+    let mut queue = [
+        Priority::Low(0.5),
+        Priority::High,
+        Priority::Normal { weight: 1.0 },
+        Priority::Low(0.1),
+    ];
+    queue.sort();
+    assert_eq!(
+        queue.iter().map(Priority::variant_name).collect::<Vec<_>>(),
+        ["High", "Normal", "Low", "Low"]
+    );
+    assert_eq!(Priority::Low(0.5), Priority::Low(0.1));
+    assert!(Priority::High < Priority::Normal { weight: f64::NAN });
+}
+
+#[test]
+fn ord_by_fields() {
+    #[derive(Debug, Ordinal)]
+    #[ordinal(ord = "fields")]
+    enum Event {
+        #[ordinal(index = 1)]
+        Timer(u32, &'static str),
+        #[ordinal(index = 0)]
+        Input { key: char },
+        #[ordinal(index = 2)]
+        Shutdown,
+    }
+
+    let mut events = [
+        Event::Shutdown,
+        Event::Timer(2, "b"),
+        Event::Input { key: 'z' },
+        Event::Timer(2, "a"),
+        Event::Timer(1, "c"),
+        Event::Input { key: 'a' },
+    ];
+    events.sort();
+    assert_eq!(
+        events,
+        [
+            Event::Input { key: 'a' },
+            Event::Input { key: 'z' },
+            Event::Timer(1, "c"),
+            Event::Timer(2, "a"),
+            Event::Timer(2, "b"),
+            Event::Shutdown,
+        ]
+    );
+    assert_ne!(Event::Timer(1, "a"), Event::Timer(1, "b"));
+}
+
+#[test]
+fn same_variant() {
+    #[derive(Ordinal)]
+    enum Test {
+        A(f64),
+        B { x: f64 },
+        C,
+    }
+
+    const SAME: [bool; 2] = [
+        Test::C.same_variant(&Test::C),
+        Test::C.same_variant(&Test::A(0.0)),
+    ];
+    assert_eq!(SAME, [true, false]);
+    assert!(Test::A(1.0).same_variant(&Test::A(f64::NAN)));
+    assert!(Test::B { x: 1.0 }.same_variant(&Test::B { x: 2.0 }));
+    assert!(!Test::A(1.0).same_variant(&Test::B { x: 1.0 }));
+}
+
+#[test]
+fn by_variant() {
+    use ordinalizer::ByVariant;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Ordinal)]
+    enum Reading {
+        Temperature(f64),
+        Humidity(f64),
+    }
+
+    let mut latest = HashMap::new();
+    for reading in [
+        Reading::Temperature(21.5),
+        Reading::Humidity(0.4),
+        Reading::Temperature(22.0),
+    ] {
+        latest.insert(ByVariant(reading), ());
+    }
+
+    assert_eq!(latest.len(), 2);
+    assert!(latest.contains_key(&ByVariant(Reading::Humidity(0.0))));
+    assert_eq!(
+        ByVariant(Reading::Temperature(1.0)),
+        Reading::Temperature(2.0).into()
+    );
+    assert!(ByVariant(Reading::Temperature(1.0)) < ByVariant(Reading::Humidity(0.0)));
+}
+
+#[test]
+fn flatten() {
+    #[derive(Debug, PartialEq, Ordinal)]
+    enum NetMsg {
+        Ping,
+        Pong,
+    }
+
+    #[derive(Debug, PartialEq, Ordinal)]
+    enum FileMsg {
+        Open,
+        Read,
+        Close,
+    }
+
+    #[derive(Debug, PartialEq, Ordinal)]
+    #[ordinal(from_ordinal, repr = "u8")]
+    enum Msg {
+        Hello,
+        #[ordinal(flatten)]
+        Net(NetMsg),
+        #[ordinal(flatten)]
+        File {
+            msg: FileMsg,
+        },
+        Bye,
+    }
+
+    assert_eq!(Msg::VARIANT_COUNT, 7);
+    assert_eq!(<Msg as Ordinal>::VARIANT_COUNT, 7);
+    assert_eq!(Msg::Hello.ordinal(), 0);
+    assert_eq!(Msg::Net(NetMsg::Ping).ordinal(), 1);
+    assert_eq!(Msg::Net(NetMsg::Pong).ordinal(), 2);
+    assert_eq!(Msg::File { msg: FileMsg::Open }.ordinal(), 3);
+    assert_eq!(
+        Msg::File {
+            msg: FileMsg::Close
+        }
+        .ordinal(),
+        5
+    );
+    assert_eq!(Msg::Bye.ordinal(), 6);
+    assert_eq!(Msg::ORDINAL_FILE, 3);
+    assert_eq!(Msg::ORDINAL_BYE, 6);
+
+    assert_eq!(
+        Msg::VARIANT_NAMES,
+        ["Hello", "Ping", "Pong", "Open", "Read", "Close", "Bye"]
+    );
+    assert_eq!(Msg::Net(NetMsg::Pong).variant_name(), "Pong");
+
+    assert_eq!(Msg::from_ordinal(2), Some(Msg::Net(NetMsg::Pong)));
+    assert_eq!(Msg::from_ordinal(4), Some(Msg::File { msg: FileMsg::Read }));
+    assert_eq!(Msg::from_ordinal(6), Some(Msg::Bye));
+    assert_eq!(Msg::from_ordinal(7), None);
+
+    assert!(Msg::Net(NetMsg::Ping).same_variant(&Msg::Net(NetMsg::Ping)));
+    assert!(!Msg::Net(NetMsg::Ping).same_variant(&Msg::Net(NetMsg::Pong)));
+    assert!(!Msg::Net(NetMsg::Ping).same_variant(&Msg::Hello));
+    assert!(Msg::Bye.same_variant(&Msg::Bye));
+    assert_ne!(
+        ordinalizer::ByVariant(Msg::Net(NetMsg::Ping)),
+        ordinalizer::ByVariant(Msg::Net(NetMsg::Pong))
+    );
+
+    let set = Msg::ordinal_set(&[Msg::Net(NetMsg::Pong), Msg::Bye]);
+    assert_eq!(set.iter().collect::<Vec<_>>(), [2, 6]);
+}
+
+mod configured {
+    use ordinalizer::Ordinal;
+
+    #[derive(Ordinal)]
+    #[ordinal(
+        method = "index",
+        vis = "pub(crate)",
+        attrs(inline, must_use, doc = "Generated by `#[derive(Ordinal)]`."),
+        kind = "InstructionKind",
+        ord
+    )]
+    pub enum Instruction {
+        Push(u8),
+        Pop,
+    }
+
+    impl Instruction {
+        // An existing method which would clash with the default name.
+        pub fn ordinal(&self) -> &'static str {
+            "user-defined"
+        }
+    }
+}
+
+#[test]
+fn method_and_vis() {
+    use configured::{Instruction, InstructionKind};
+
+    assert_eq!(Instruction::Pop.index(), 1);
+    assert_eq!(Instruction::Pop.ordinal(), "user-defined");
+    assert_eq!(Ordinal::ordinal(&Instruction::Push(3)), 0);
+    assert_eq!(Instruction::VARIANT_COUNT, 2);
+    assert_eq!(Instruction::Pop.kind().index(), 1);
+    assert_eq!(InstructionKind::Push.index(), 0);
+    assert!(Instruction::Push(9) < Instruction::Pop);
+}
+
+#[test]
+fn all() {
+    #[derive(Debug, PartialEq, Ordinal)]
+    enum Test {
+        #[ordinal(index = 2)]
+        C,
+        #[ordinal(index = 0)]
+        A,
+        #[ordinal(skip)]
+        Hidden,
+        #[ordinal(index = 1)]
+        B,
+    }
+
+    assert_eq!(Test::ALL, [Test::A, Test::B, Test::C]);
+    assert_eq!(Test::all().collect::<Vec<_>>(), Test::ALL);
+    assert_eq!(Test::all().next_back(), Some(Test::C));
+    assert_eq!(Test::all().len(), 3);
+    assert_eq!(Test::all().nth(1), Some(Test::B));
+    assert!(Test::all().all(|test| test.ordinal().is_some()));
+}
+
+#[test]
+fn navigation() {
+    #[derive(Debug, PartialEq, Ordinal)]
+    enum Light {
+        #[ordinal(index = 2)]
+        Red,
+        #[ordinal(index = 0)]
+        Green,
+        #[ordinal(skip)]
+        Off,
+        #[ordinal(index = 1)]
+        Yellow,
+    }
+
+    const AFTER_GREEN: Option<Light> = Light::Green.next();
+    assert_eq!(AFTER_GREEN, Some(Light::Yellow));
+    assert_eq!(Light::first(), Light::Green);
+    assert_eq!(Light::last(), Light::Red);
+    assert_eq!(Light::Red.next(), None);
+    assert_eq!(Light::Red.prev(), Some(Light::Yellow));
+    assert_eq!(Light::Green.prev(), None);
+    assert_eq!(Light::Red.wrapping_next(), Light::Green);
+    assert_eq!(Light::Green.wrapping_prev(), Light::Red);
+    assert_eq!(Light::Off.next(), None);
+    assert_eq!(Light::Off.wrapping_next(), Light::Green);
+    assert_eq!(Light::Off.wrapping_prev(), Light::Red);
+
+    // A skipped variant may carry fields with destructors.
+    #[derive(Debug, PartialEq, Ordinal)]
+    #[ordinal(on_skip = "panic")]
+    enum Step {
+        Start,
+        #[ordinal(skip)]
+        Custom(String),
+        Stop,
+    }
+
+    assert_eq!(Step::Stop.wrapping_next(), Step::Start);
+    assert_eq!(Step::Custom(String::new()).wrapping_prev(), Step::Stop);
+}
+
+#[test]
+fn lock() {
+    #[derive(Debug, PartialEq, Ordinal)]
+    #[ordinal(lock = "tests/ordinals.lock")]
+    enum Status {
+        Active,
+        Suspended,
+        #[ordinal(skip)]
+        Unknown,
+        Deleted,
+    }
+
+    assert_eq!(Status::Deleted.ordinal(), Some(2));
+}
+
+#[test]
+fn variant_info() {
+    #[derive(Ordinal)]
+    enum Inner {
+        A,
+        B,
+    }
+
+    #[derive(Ordinal)]
+    enum Shape {
+        Point,
+        Circle(f64),
+        #[ordinal(skip)]
+        Unknown,
+        Rect {
+            width: f64,
+            r#height: f64,
+        },
+        #[ordinal(flatten)]
+        Inner(Inner),
+    }
+
+    assert_eq!(
+        Shape::VARIANTS,
+        [
+            VariantInfo::new("Point", Some(0), FieldStyle::Unit, 0, &[]),
+            VariantInfo::new("Circle", Some(1), FieldStyle::Tuple, 1, &[]),
+            VariantInfo::new("Unknown", None, FieldStyle::Unit, 0, &[]),
+            VariantInfo::new("Rect", Some(2), FieldStyle::Named, 2, &["width", "height"]),
+            VariantInfo::new("Inner", Some(3), FieldStyle::Tuple, 1, &[]),
+        ]
+    );
+
+    const CIRCLE: &VariantInfo = Shape::Circle(1.0).variant_info();
+    assert_eq!(CIRCLE.name(), "Circle");
+    assert_eq!(Shape::Unknown.variant_info().ordinal(), None);
+    let rect = Shape::Rect {
+        width: 1.0,
+        height: 2.0,
+    };
+    assert_eq!(rect.variant_info().field_names(), ["width", "height"]);
+    assert_eq!(Shape::Inner(Inner::B).variant_info().ordinal(), Some(3));
+    assert_eq!(Shape::Inner(Inner::B).ordinal(), Some(4));
+}
@@ -0,0 +1,92 @@
+#![allow(dead_code)]
+
+use ordinalizer::{Ordinal, OrdinalSet};
+
+#[derive(Debug, Ordinal)]
+enum Message {
+    Ping,
+    Chat(String),
+    Move { x: f64, y: f64 },
+    Quit,
+}
+
+#[rustfmt::skip]
+#[derive(Ordinal)]
+enum Large {
+    V0, V1, V2, V3, V4, V5, V6, V7, V8, V9,
+    V10, V11, V12, V13, V14, V15, V16, V17, V18, V19,
+    V20, V21, V22, V23, V24, V25, V26, V27, V28, V29,
+    V30, V31, V32, V33, V34, V35, V36, V37, V38, V39,
+    V40, V41, V42, V43, V44, V45, V46, V47, V48, V49,
+    V50, V51, V52, V53, V54, V55, V56, V57, V58, V59,
+    V60, V61, V62, V63, V64, V65, V66, V67, V68, V69,
+    V70, V71, V72, V73, V74, V75, V76, V77, V78, V79,
+    V80, V81, V82, V83, V84, V85, V86, V87, V88, V89,
+    V90, V91, V92, V93, V94, V95, V96, V97, V98, V99,
+    V100, V101, V102, V103, V104, V105, V106, V107, V108, V109,
+    V110, V111, V112, V113, V114, V115, V116, V117, V118, V119,
+    V120, V121, V122, V123, V124, V125, V126, V127, V128, V129,
+}
+
+#[test]
+fn insert_and_contains() {
+    let mut set = OrdinalSet::new();
+    assert!(set.is_empty());
+    assert!(set.insert(&Message::Chat("hi".into())));
+    assert!(!set.insert(&Message::Chat(String::new())));
+    assert!(set.insert(&Message::Quit));
+
+    assert!(set.contains(&Message::Chat("other".into())));
+    assert!(!set.contains(&Message::Move { x: 0.0, y: 0.0 }));
+    assert_eq!(set.len(), 2);
+    assert_eq!(set.bits(), 0b1010u8);
+
+    assert!(set.remove(&Message::Quit));
+    assert!(!set.remove(&Message::Quit));
+    assert_eq!(set.iter().collect::<Vec<_>>(), [1]);
+}
+
+#[test]
+fn set_operations() {
+    let a: OrdinalSet<Message> = vec![Message::Ping, Message::Quit].into_iter().collect();
+    let b: OrdinalSet<Message> = vec![Message::Ping, Message::Chat(String::new())]
+        .into_iter()
+        .collect();
+
+    assert_eq!((a | b).iter().collect::<Vec<_>>(), [0, 1, 3]);
+    assert_eq!((a & b).iter().collect::<Vec<_>>(), [0]);
+    assert_eq!((a - b).iter().collect::<Vec<_>>(), [3]);
+    assert_eq!((a ^ b).iter().collect::<Vec<_>>(), [1, 3]);
+    assert_eq!((!a).iter().collect::<Vec<_>>(), [1, 2]);
+    assert_eq!(OrdinalSet::<Message>::all().len(), 4);
+    assert!((a & b).is_subset(&a));
+    assert!(a.is_superset(&(a & b)));
+    assert!((a - b).is_disjoint(&b));
+    assert_eq!(format!("{:?}", a), "{0, 3}");
+}
+
+#[test]
+fn const_construction() {
+    const CONTROL: OrdinalSet<Message> = Message::ordinal_set(&[Message::Ping, Message::Quit]);
+    const EMPTY: OrdinalSet<Message> = OrdinalSet::new();
+
+    assert_eq!(CONTROL.bits(), 0b1001);
+    assert!(EMPTY.is_empty());
+    assert_eq!(CONTROL.iter().rev().collect::<Vec<_>>(), [3, 0]);
+}
+
+#[test]
+fn word_storage() {
+    const EDGES: OrdinalSet<Large> = Large::ordinal_set(&[Large::V0, Large::V64, Large::V129]);
+
+    assert_eq!(EDGES.bits(), [1, 1, 2]);
+    assert!(EDGES.contains(&Large::V129));
+    assert!(!EDGES.contains(&Large::V128));
+    assert_eq!(EDGES.iter().collect::<Vec<_>>(), [0, 64, 129]);
+    assert_eq!(EDGES.iter().rev().collect::<Vec<_>>(), [129, 64, 0]);
+
+    let complement = !EDGES;
+    assert_eq!(complement.len(), 127);
+    assert!(!complement.contains(&Large::V64));
+    assert_eq!(OrdinalSet::<Large>::all().len(), 130);
+}